@@ -0,0 +1,521 @@
+use crate::clock;
+use crate::parsers::{Fissure, FissureTier, Invasion, Reward};
+use crate::voidrat::data_path;
+use rusqlite::{params, Connection, Row};
+use std::fs;
+
+const ARCHIVE_FILE: &str = "history.sqlite";
+
+/// Fissure as stored in the archive.
+#[derive(Debug, Clone)]
+pub struct FissureRecord {
+    pub node: String,
+    pub mission: String,
+    pub tier: FissureTier,
+    pub is_storm: bool,
+    pub hard: bool,
+    /// Fissure activation time in seconds.
+    pub activation: i64,
+    /// Fissure expiry time in seconds.
+    pub expiry: i64,
+    /// When the fissure was first fetched in seconds.
+    pub first_seen: i64,
+    /// When the fissure was last fetched in seconds.
+    pub last_seen: i64,
+}
+
+impl FissureRecord {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(FissureRecord {
+            node: row.get(0)?,
+            mission: row.get(1)?,
+            tier: FissureTier::from_str(&row.get::<_, String>(2)?),
+            is_storm: row.get(3)?,
+            hard: row.get(4)?,
+            activation: row.get(5)?,
+            expiry: row.get(6)?,
+            first_seen: row.get(7)?,
+            last_seen: row.get(8)?,
+        })
+    }
+}
+
+/// Invasion as stored in the archive.
+#[derive(Debug, Clone)]
+pub struct InvasionRecord {
+    pub node: String,
+    /// Attacker rewards joined with `, `.
+    pub attacker: String,
+    /// Defender rewards joined with `, `.
+    pub defender: String,
+    /// Invasion activation time in seconds.
+    pub activation: i64,
+    /// When the invasion was first fetched in seconds.
+    pub first_seen: i64,
+    /// When the invasion was last fetched in seconds.
+    pub last_seen: i64,
+}
+
+impl InvasionRecord {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(InvasionRecord {
+            node: row.get(0)?,
+            attacker: row.get(1)?,
+            defender: row.get(2)?,
+            activation: row.get(3)?,
+            first_seen: row.get(4)?,
+            last_seen: row.get(5)?,
+        })
+    }
+
+    /// All the rewards, e.g: `Forma Blueprint, 3 Fieldron`.
+    pub fn rewards(&self) -> String {
+        [self.defender.as_str(), self.attacker.as_str()]
+            .iter()
+            .filter(|r| !r.is_empty())
+            .cloned()
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+}
+
+/// How often fissures show up on a node.
+#[derive(Debug, Clone)]
+pub struct NodeCount {
+    pub node: String,
+    pub mission: String,
+    /// Normal fissures, Steel Path included.
+    pub total: u32,
+    /// Steel Path fissures.
+    pub hard: u32,
+    /// Void Storms.
+    pub storm: u32,
+}
+
+/// Filters for the archived fissures. Empty filters match everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FissureQuery {
+    /// Part of the node name, e.g: `Hepit`.
+    pub node: String,
+    /// Part of the mission type, e.g: `Capture`.
+    pub mission: String,
+    pub tier: Option<FissureTier>,
+    pub is_storm: Option<bool>,
+    pub hard: Option<bool>,
+}
+
+/// Every fissure and invasion seen, stored in a SQLite database in the data directory.
+pub struct Archive {
+    conn: Connection,
+}
+
+impl Archive {
+    /// Open the archive, creating the database if it does not exist.
+    pub fn open() -> rusqlite::Result<Self> {
+        fs::create_dir_all(data_path()).expect("Cannot create the data directory.");
+
+        Self::with_connection(Connection::open(data_path().join(ARCHIVE_FILE))?)
+    }
+
+    /// Archive kept in memory only, gone when it is dropped.
+    #[cfg(test)]
+    fn in_memory() -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    /// Create the tables the `conn` does not have yet.
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS fissures (
+                node TEXT NOT NULL,
+                mission TEXT NOT NULL,
+                tier TEXT NOT NULL,
+                is_storm INTEGER NOT NULL,
+                hard INTEGER NOT NULL,
+                activation INTEGER NOT NULL,
+                expiry INTEGER NOT NULL,
+                first_seen INTEGER NOT NULL,
+                last_seen INTEGER NOT NULL,
+                PRIMARY KEY (node, tier, is_storm, hard, activation)
+            );
+            CREATE TABLE IF NOT EXISTS invasions (
+                node TEXT NOT NULL,
+                attacker TEXT NOT NULL,
+                defender TEXT NOT NULL,
+                activation INTEGER NOT NULL,
+                first_seen INTEGER NOT NULL,
+                last_seen INTEGER NOT NULL,
+                PRIMARY KEY (node, activation)
+            );",
+        )?;
+
+        Ok(Archive { conn })
+    }
+
+    /// Store the fissures and invasions.
+    /// Already archived ones only get their last seen time updated.
+    pub fn record(&mut self, fissures: &[Fissure], invasions: &[Invasion]) -> rusqlite::Result<()> {
+        let now = clock::now().timestamp();
+        let tx = self.conn.transaction()?;
+
+        for f in fissures {
+            tx.execute(
+                "INSERT INTO fissures VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)
+                ON CONFLICT DO UPDATE SET last_seen = ?8",
+                params![
+                    f.node.value,
+                    f.mission,
+                    f.tier.to_string(),
+                    f.is_storm,
+                    f.hard,
+                    f.activation.timestamp(),
+                    f.expiry.timestamp(),
+                    now
+                ],
+            )?;
+        }
+
+        for i in invasions {
+            tx.execute(
+                "INSERT INTO invasions VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+                ON CONFLICT DO UPDATE SET last_seen = ?5",
+                params![
+                    i.node.value,
+                    join_rewards(&i.rewards.attacker),
+                    join_rewards(&i.rewards.defender),
+                    i.activation.timestamp(),
+                    now
+                ],
+            )?;
+        }
+
+        tx.commit()
+    }
+
+    /// Remove everything not seen in `days`.
+    /// Returns how many rows were removed.
+    pub fn prune(&self, days: u32) -> rusqlite::Result<usize> {
+        let oldest = clock::now().timestamp() - days as i64 * 24 * 60 * 60;
+
+        Ok(self
+            .conn
+            .execute("DELETE FROM fissures WHERE last_seen < ?1", [oldest])?
+            + self
+                .conn
+                .execute("DELETE FROM invasions WHERE last_seen < ?1", [oldest])?)
+    }
+
+    /// Returns the archived fissures matching the `query`, newest first.
+    pub fn fissures(&self, query: &FissureQuery) -> rusqlite::Result<Vec<FissureRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT node, mission, tier, is_storm, hard, activation, expiry, first_seen, last_seen
+            FROM fissures
+            WHERE node LIKE ?1 ESCAPE '\\' AND mission LIKE ?2 ESCAPE '\\'
+                AND (?3 IS NULL OR tier = ?3)
+                AND (?4 IS NULL OR is_storm = ?4)
+                AND (?5 IS NULL OR hard = ?5)
+            ORDER BY activation DESC",
+        )?;
+
+        let rows = stmt.query_map(
+            params![
+                like(&query.node),
+                like(&query.mission),
+                query.tier.as_ref().map(|t| t.to_string()),
+                query.is_storm,
+                query.hard
+            ],
+            FissureRecord::from_row,
+        )?;
+
+        rows.collect()
+    }
+
+    /// Returns the archived invasions with a reward containing `reward`, newest first.
+    pub fn invasions(&self, reward: &str) -> rusqlite::Result<Vec<InvasionRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT node, attacker, defender, activation, first_seen, last_seen
+            FROM invasions
+            WHERE attacker LIKE ?1 ESCAPE '\\' OR defender LIKE ?1 ESCAPE '\\'
+            ORDER BY activation DESC",
+        )?;
+
+        let rows = stmt.query_map([like(reward)], InvasionRecord::from_row)?;
+
+        rows.collect()
+    }
+}
+
+/// Aggregates for the statistics.
+impl Archive {
+    /// Returns how many normal fissures of each tier were activated per day.
+    /// Days are counted from the UNIX epoch.
+    pub fn tier_counts_per_day(&self) -> rusqlite::Result<Vec<(i64, FissureTier, u32)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT activation / 86400 AS day, tier, COUNT(*) FROM fissures
+            WHERE is_storm = 0
+            GROUP BY day, tier
+            ORDER BY day",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                FissureTier::from_str(&row.get::<_, String>(1)?),
+                row.get(2)?,
+            ))
+        })?;
+
+        rows.collect()
+    }
+
+    /// Returns how many normal fissures of each tier had each mission type.
+    pub fn mission_counts(&self) -> rusqlite::Result<Vec<(FissureTier, String, u32)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tier, mission, COUNT(*) AS count FROM fissures
+            WHERE is_storm = 0
+            GROUP BY tier, mission
+            ORDER BY count DESC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                FissureTier::from_str(&row.get::<_, String>(0)?),
+                row.get(1)?,
+                row.get(2)?,
+            ))
+        })?;
+
+        rows.collect()
+    }
+
+    /// Returns the average lifetime in seconds of normal fissures for each tier.
+    pub fn average_lifetimes(&self) -> rusqlite::Result<Vec<(FissureTier, f64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tier, AVG(expiry - activation) FROM fissures
+            WHERE is_storm = 0
+            GROUP BY tier",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                FissureTier::from_str(&row.get::<_, String>(0)?),
+                row.get(1)?,
+            ))
+        })?;
+
+        rows.collect()
+    }
+
+    /// Returns how often fissures, Steel Path fissures and Void Storms showed up on each node.
+    pub fn node_counts(&self) -> rusqlite::Result<Vec<NodeCount>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT node, mission,
+                SUM(is_storm = 0) AS total, SUM(hard), SUM(is_storm)
+            FROM fissures
+            GROUP BY node, mission
+            ORDER BY total DESC, node",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(NodeCount {
+                node: row.get(0)?,
+                mission: row.get(1)?,
+                total: row.get(2)?,
+                hard: row.get(3)?,
+                storm: row.get(4)?,
+            })
+        })?;
+
+        rows.collect()
+    }
+
+    /// Returns the rewards of every archived invasion, e.g: `3 Fieldron, Forma Blueprint`.
+    pub fn invasion_rewards(&self) -> rusqlite::Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT node, attacker, defender, activation, first_seen, last_seen FROM invasions",
+        )?;
+
+        let rows = stmt.query_map([], |row| InvasionRecord::from_row(row).map(|i| i.rewards()))?;
+
+        rows.collect()
+    }
+}
+
+/// Wrap the text in wildcards for a `LIKE` query.
+/// `%` and `_` in the text are escaped with `\`, so they only match themselves.
+fn like(text: &str) -> String {
+    let mut pattern = String::from("%");
+    for c in text.trim().chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+
+    pattern
+}
+
+/// Rewards joined with `, `, e.g: `3 Fieldron, Forma Blueprint`.
+pub(crate) fn join_rewards(rewards: &[Reward]) -> String {
+    rewards
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    /// Fissure that lasts 90 minutes from `hours_ago`.
+    fn fissure(node: &str, tier: FissureTier, hours_ago: i64) -> Fissure {
+        let activation = Utc::now() - Duration::hours(hours_ago);
+
+        Fissure {
+            activation,
+            expiry: activation + Duration::minutes(90),
+            ..Fissure::test(node, tier)
+        }
+    }
+
+    fn archive() -> Archive {
+        let mut archive = Archive::in_memory().unwrap();
+        let mut tessera = Invasion::test("Tessera (Venus)", "100% Fieldron_Sample");
+        std::mem::swap(&mut tessera.rewards.attacker, &mut tessera.rewards.defender);
+
+        archive
+            .record(
+                &[
+                    fissure("Hepit (Void)", FissureTier::Lith, 0),
+                    fissure("Ukko (Void)", FissureTier::Axi, 1),
+                    Fissure {
+                        mission: "Interception".to_string(),
+                        ..fissure("Cinxia (Ceres)", FissureTier::Meso, 2)
+                    },
+                ],
+                &[
+                    Invasion::test("Kiliken (Venus)", "Forma Blueprint"),
+                    tessera,
+                ],
+            )
+            .unwrap();
+
+        archive
+    }
+
+    fn nodes(records: &[FissureRecord]) -> Vec<&str> {
+        records.iter().map(|r| r.node.as_str()).collect()
+    }
+
+    #[test]
+    fn fissures_match_the_query_newest_first() {
+        let archive = archive();
+
+        let all = archive.fissures(&FissureQuery::default()).unwrap();
+        assert_eq!(
+            nodes(&all),
+            ["Hepit (Void)", "Ukko (Void)", "Cinxia (Ceres)"]
+        );
+
+        let void = FissureQuery {
+            node: "void".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            nodes(&archive.fissures(&void).unwrap()),
+            ["Hepit (Void)", "Ukko (Void)"]
+        );
+
+        let axi_capture = FissureQuery {
+            mission: "capt".to_string(),
+            tier: Some(FissureTier::Axi),
+            ..Default::default()
+        };
+        assert_eq!(
+            nodes(&archive.fissures(&axi_capture).unwrap()),
+            ["Ukko (Void)"]
+        );
+    }
+
+    #[test]
+    fn wildcards_in_the_query_match_only_themselves() {
+        let archive = archive();
+
+        for text in ["%", "_", "\\"] {
+            let query = FissureQuery {
+                node: text.to_string(),
+                ..Default::default()
+            };
+            assert!(archive.fissures(&query).unwrap().is_empty(), "{}", text);
+        }
+
+        assert_eq!(archive.invasions("%").unwrap().len(), 1);
+        assert_eq!(archive.invasions("n_s").unwrap().len(), 1);
+        assert!(archive.invasions("a_B").unwrap().is_empty());
+    }
+
+    #[test]
+    fn recording_again_only_updates_the_last_seen_time() {
+        let mut archive = Archive::in_memory().unwrap();
+        let fissures = [fissure("Hepit (Void)", FissureTier::Lith, 0)];
+
+        archive.record(&fissures, &[]).unwrap();
+        archive.record(&fissures, &[]).unwrap();
+
+        assert_eq!(archive.fissures(&FissureQuery::default()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn invasions_match_either_side() {
+        let archive = archive();
+
+        let forma = archive.invasions("forma").unwrap();
+        assert_eq!(forma.len(), 1);
+        assert_eq!(forma[0].node, "Kiliken (Venus)");
+        assert_eq!(forma[0].rewards(), "Forma Blueprint");
+        assert_eq!(archive.invasions("").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn prune_removes_what_was_not_seen_recently() {
+        let archive = archive();
+        let old = clock::now().timestamp() - 3 * 24 * 60 * 60;
+        archive
+            .conn
+            .execute(
+                "UPDATE fissures SET last_seen = ?1 WHERE tier = 'Lith'",
+                [old],
+            )
+            .unwrap();
+
+        assert_eq!(archive.prune(2).unwrap(), 1);
+        assert_eq!(archive.prune(2).unwrap(), 0);
+        assert_eq!(archive.fissures(&FissureQuery::default()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn statistics_count_the_normal_fissures() {
+        let archive = archive();
+
+        let capture = archive
+            .mission_counts()
+            .unwrap()
+            .into_iter()
+            .filter(|(_, mission, _)| mission == "Capture")
+            .count();
+        assert_eq!(capture, 2);
+
+        let lifetimes = archive.average_lifetimes().unwrap();
+        assert!(lifetimes.iter().all(|(_, seconds)| *seconds == 90.0 * 60.0));
+
+        let nodes = archive.node_counts().unwrap();
+        assert_eq!(nodes.len(), 3);
+        assert!(nodes
+            .iter()
+            .all(|n| n.total == 1 && n.hard == 0 && n.storm == 0));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::parsers::world_state::WorldState;
+use crate::parsers::{SolarNode, SolarNodes, TennoParser};
+use crate::util::Resources;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How many times the world state is parsed.
+const ROUNDS: u32 = 20;
+
+/// Print the average time it takes to look up the solar nodes of the world state in the `path`,
+/// the way it was done before the shared index and with it, and to parse the fissures and invasions.
+pub fn run(path: &Path) -> Result<(), String> {
+    let data = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let keys = node_keys(&data)?;
+    let parser = WorldState {};
+
+    let index = time(|| {
+        black_box(SolarNodes::bundled());
+    });
+    let old_lookups = time(|| {
+        for key in &keys {
+            black_box(old_node_by_key(key));
+        }
+    });
+    let lookups = time(|| {
+        let nodes = SolarNodes::shared();
+        for key in &keys {
+            black_box(nodes.get(key).cloned().unwrap_or_default());
+        }
+    });
+    let parse = time(|| {
+        black_box(parser.parse_fissures(&data));
+        black_box(parser.parse_invasions(&data));
+    });
+
+    println!(
+        "{} node lookups, {} nodes",
+        keys.len(),
+        SolarNodes::shared().len()
+    );
+    println!("Building the node index: {:?}", index);
+    println!(
+        "Node lookups parsing the node data every time: {:?} per world state",
+        old_lookups
+    );
+    println!(
+        "Node lookups in the shared index: {:?} per world state",
+        lookups
+    );
+    println!(
+        "Parsing the fissures and invasions: {:?} per world state",
+        parse
+    );
+
+    Ok(())
+}
+
+/// Returns the node keys the parser looks up, in the same order.
+/// Void storms looked up their node twice before the shared index.
+fn node_keys(data: &str) -> Result<Vec<String>, String> {
+    let v: Value = serde_json::from_str(data).map_err(|e| e.to_string())?;
+    let nodes = |name: &str| -> Vec<String> {
+        v[name]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|i| i["Node"].as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let storms = nodes("VoidStorms");
+    let mut keys = nodes("Invasions");
+    keys.extend(nodes("ActiveMissions"));
+    keys.extend(storms.iter().cloned());
+    keys.extend(storms);
+
+    Ok(keys)
+}
+
+/// The node lookup before the shared index: the bundled node data is parsed for every lookup.
+fn old_node_by_key(key: &str) -> SolarNode {
+    let sol_data = Resources::get("data/sol_node.json").unwrap().data;
+    let nodes: HashMap<String, SolarNode> = serde_json::from_slice(&sol_data).expect("Bad JSON.");
+
+    nodes.get(key).cloned().unwrap_or_default()
+}
+
+/// Returns the average time of running `f` `ROUNDS` times.
+fn time(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+
+    start.elapsed() / ROUNDS
+}
@@ -0,0 +1,186 @@
+use chrono::{DateTime, Duration, Local, Utc};
+use parking_lot::{Mutex, RwLock};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Source of the current time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Returns true if the time is not the real time.
+    fn is_virtual(&self) -> bool {
+        false
+    }
+}
+
+/// The real time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+#[derive(Debug)]
+struct VirtualState {
+    /// Virtual time when the state was last changed.
+    base: DateTime<Utc>,
+    /// Real time when the state was last changed.
+    base_instant: Instant,
+    /// How many virtual seconds pass in a real second.
+    speed: f64,
+    paused: bool,
+}
+
+/// Clock that can be set to any time, run faster than the real time or be paused.
+#[derive(Debug)]
+pub struct VirtualClock {
+    state: Mutex<VirtualState>,
+}
+
+impl VirtualClock {
+    /// New paused clock at the `time`.
+    pub fn new(time: DateTime<Utc>) -> Self {
+        VirtualClock {
+            state: Mutex::new(VirtualState {
+                base: time,
+                base_instant: Instant::now(),
+                speed: 1.0,
+                paused: true,
+            }),
+        }
+    }
+
+    /// Move the clock to the `time`.
+    pub fn set(&self, time: DateTime<Utc>) {
+        let mut state = self.state.lock();
+        state.base = time;
+        state.base_instant = Instant::now();
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.state.lock().speed
+    }
+
+    pub fn set_speed(&self, speed: f64) {
+        let now = self.now();
+        let mut state = self.state.lock();
+        state.base = now;
+        state.base_instant = Instant::now();
+        state.speed = speed;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().paused
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        let now = self.now();
+        let mut state = self.state.lock();
+        state.base = now;
+        state.base_instant = Instant::now();
+        state.paused = paused;
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> DateTime<Utc> {
+        let state = self.state.lock();
+        if state.paused {
+            return state.base;
+        }
+
+        let elapsed = state.base_instant.elapsed().as_secs_f64() * state.speed;
+
+        state.base + Duration::milliseconds((elapsed * 1000.0) as i64)
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+}
+
+/// Clock used by the whole app, `None` for the system clock.
+static CLOCK: RwLock<Option<Arc<dyn Clock>>> = RwLock::new(None);
+
+/// Use the `clock` instead of the system clock everywhere.
+pub fn set(clock: Arc<dyn Clock>) {
+    *CLOCK.write() = Some(clock);
+}
+
+/// Returns the current time of the app clock.
+pub fn now() -> DateTime<Utc> {
+    match CLOCK.read().as_ref() {
+        Some(clock) => clock.now(),
+        None => SystemClock.now(),
+    }
+}
+
+/// Returns the current time of the app clock in the local time zone.
+pub fn local_now() -> DateTime<Local> {
+    now().with_timezone(&Local)
+}
+
+/// Returns true if the app clock is not the real time, e.g: during a replay.
+pub fn is_virtual() -> bool {
+    CLOCK.read().as_ref().is_some_and(|c| c.is_virtual())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::thread;
+
+    fn start() -> DateTime<Utc> {
+        Utc.ymd(2022, 10, 1).and_hms(12, 0, 0)
+    }
+
+    #[test]
+    fn paused_clocks_stand_still() {
+        let clock = VirtualClock::new(start());
+        assert!(clock.is_paused());
+
+        thread::sleep(std::time::Duration::from_millis(20));
+        assert_eq!(clock.now(), start());
+
+        clock.set(start() + Duration::hours(1));
+        assert_eq!(clock.now(), start() + Duration::hours(1));
+        assert!(clock.is_virtual());
+    }
+
+    #[test]
+    fn running_clocks_move_at_the_speed() {
+        let clock = VirtualClock::new(start());
+        clock.set_speed(3600.0);
+        clock.set_paused(false);
+
+        thread::sleep(std::time::Duration::from_millis(50));
+        let elapsed = clock.now() - start();
+
+        // At least 50 ms worth of virtual hours, with some slack for a slow machine.
+        assert!(elapsed >= Duration::seconds(180), "{}", elapsed);
+        assert!(elapsed < Duration::hours(1), "{}", elapsed);
+        assert_eq!(clock.speed(), 3600.0);
+    }
+
+    #[test]
+    fn pausing_keeps_the_time_reached() {
+        let clock = VirtualClock::new(start());
+        clock.set_speed(3600.0);
+        clock.set_paused(false);
+        thread::sleep(std::time::Duration::from_millis(20));
+
+        clock.set_paused(true);
+        let paused_at = clock.now();
+        assert!(paused_at > start());
+
+        thread::sleep(std::time::Duration::from_millis(20));
+        assert_eq!(clock.now(), paused_at);
+
+        // Changing the speed while paused does not move the clock.
+        clock.set_speed(1.0);
+        assert_eq!(clock.now(), paused_at);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::theme::{self, BadgeColors, Theme};
+use chrono::Duration;
+use std::fmt::{self, Display, Formatter};
+
+/// Badge color of the theme a threshold maps to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum BadgeColor {
+    Urgent,
+    Soon,
+    Later,
+    Plenty,
+    Neutral,
+    VoidCapture,
+}
+
+impl BadgeColor {
+    pub const ALL: [BadgeColor; 6] = [
+        BadgeColor::Urgent,
+        BadgeColor::Soon,
+        BadgeColor::Later,
+        BadgeColor::Plenty,
+        BadgeColor::Neutral,
+        BadgeColor::VoidCapture,
+    ];
+
+    /// Returns the colors of the `theme`.
+    pub fn colors(&self, theme: &Theme) -> BadgeColors {
+        match self {
+            BadgeColor::Urgent => theme.urgent,
+            BadgeColor::Soon => theme.soon,
+            BadgeColor::Later => theme.later,
+            BadgeColor::Plenty => theme.plenty,
+            BadgeColor::Neutral => theme.neutral,
+            BadgeColor::VoidCapture => theme.void_capture,
+        }
+    }
+}
+
+impl Display for BadgeColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BadgeColor::Urgent => "Urgent",
+            BadgeColor::Soon => "Soon",
+            BadgeColor::Later => "Later",
+            BadgeColor::Plenty => "Plenty",
+            BadgeColor::Neutral => "Neutral",
+            BadgeColor::VoidCapture => "Void Capture",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Use the `color` when less than `minutes` are left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct Threshold {
+    pub minutes: i64,
+    pub color: BadgeColor,
+}
+
+/// Badge colors of the countdowns of one view.
+#[derive(Debug, Clone, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct Thresholds {
+    /// Smallest first.
+    pub thresholds: Vec<Threshold>,
+    /// Color when no threshold matches.
+    pub otherwise: BadgeColor,
+    /// Show how much of the lifetime has elapsed next to the countdown.
+    pub progress_ring: bool,
+}
+
+impl Thresholds {
+    /// Under 10, 20 and 40 minutes, blue for the rest.
+    pub fn minutes() -> Self {
+        Thresholds {
+            thresholds: vec![
+                Threshold {
+                    minutes: 10,
+                    color: BadgeColor::Urgent,
+                },
+                Threshold {
+                    minutes: 20,
+                    color: BadgeColor::Soon,
+                },
+                Threshold {
+                    minutes: 40,
+                    color: BadgeColor::Later,
+                },
+            ],
+            otherwise: BadgeColor::Plenty,
+            progress_ring: false,
+        }
+    }
+
+    /// Same color for every duration.
+    pub fn fixed(color: BadgeColor) -> Self {
+        Thresholds {
+            thresholds: vec![],
+            otherwise: color,
+            progress_ring: false,
+        }
+    }
+
+    /// Returns the color of the first threshold `dur` is under.
+    pub fn color(&self, dur: &Duration) -> BadgeColor {
+        let minutes = dur.num_minutes();
+
+        self.thresholds
+            .iter()
+            .find(|t| minutes < t.minutes)
+            .map_or(self.otherwise, |t| t.color)
+    }
+
+    /// Returns the fill and border of the current theme for the `dur`.
+    pub fn colors(&self, dur: &Duration) -> BadgeColors {
+        self.color(dur).colors(&theme::current())
+    }
+
+    /// Keep the thresholds smallest first.
+    pub fn sort(&mut self) {
+        self.thresholds.sort_by_key(|t| t.minutes);
+    }
+}
+
+/// Threshold tables of all the views.
+#[derive(Debug, Clone, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct Countdowns {
+    /// Time left of the fissures.
+    pub fissures: Thresholds,
+    /// Time left of the void storms.
+    pub storms: Thresholds,
+    /// Time left of the Cetus day or night.
+    pub cycles: Thresholds,
+    /// Time the invasions have been running.
+    pub invasions: Thresholds,
+}
+
+impl Countdowns {
+    /// Keep the thresholds of every view smallest first.
+    pub fn sort(&mut self) {
+        for thresholds in [
+            &mut self.fissures,
+            &mut self.storms,
+            &mut self.cycles,
+            &mut self.invasions,
+        ] {
+            thresholds.sort();
+        }
+    }
+}
+
+impl Default for Countdowns {
+    fn default() -> Self {
+        Countdowns {
+            fissures: Thresholds::minutes(),
+            storms: Thresholds::minutes(),
+            cycles: Thresholds::minutes(),
+            invasions: Thresholds::fixed(BadgeColor::Neutral),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use crate::parsers::{SolarNode, SolarNodes};
+use crate::voidrat::data_path;
+use log::warn;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DATA_PACK_FILE: &str = "data_pack.json";
+
+/// Dictionaries that are layered over the bundled data, so new nodes and items
+/// have proper names without a new release.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataPack {
+    /// Version of the pack, e.g: `2022.10.19`.
+    pub version: String,
+    /// Where the pack was downloaded from.
+    #[serde(default)]
+    pub source: String,
+    /// Solar node key to the node, same format as the bundled `sol_node.json`.
+    #[serde(default)]
+    pub nodes: HashMap<String, SolarNode>,
+    /// Item type to the item name, e.g: `/Lotus/Types/Items/MiscItems/Forma` to `Forma`.
+    #[serde(default)]
+    pub items: HashMap<String, String>,
+    /// Mission type to the mission name, e.g: `MT_CAPTURE` to `Capture`.
+    #[serde(default)]
+    pub mission_types: HashMap<String, String>,
+    /// Challenge type to the challenge name.
+    #[serde(default)]
+    pub challenges: HashMap<String, String>,
+}
+
+/// Pack used by the parsers, `None` until the first lookup.
+static DATA_PACK: RwLock<Option<Arc<DataPack>>> = RwLock::new(None);
+
+impl DataPack {
+    fn path() -> PathBuf {
+        data_path().join(DATA_PACK_FILE)
+    }
+
+    /// Load the pack from the data directory.
+    /// Returns an empty pack if there is none, so only the bundled data is used.
+    pub fn load() -> Self {
+        let json = match fs::read_to_string(Self::path()) {
+            Ok(json) => json,
+            Err(_) => return Self::default(),
+        };
+
+        Self::parse(&json).unwrap_or_else(|e| {
+            warn!("Cannot parse {}: {}", DATA_PACK_FILE, e);
+            Self::default()
+        })
+    }
+
+    /// Parse and validate the pack.
+    fn parse(json: &str) -> Result<Self, String> {
+        let pack: DataPack = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+        if pack.version.trim().is_empty() {
+            return Err("No version.".to_string());
+        }
+        if pack.is_empty() {
+            return Err("No data.".to_string());
+        }
+        if let Some((key, _)) = pack.nodes.iter().find(|(_, n)| n.value.trim().is_empty()) {
+            return Err(format!("Node {} has no name.", key));
+        }
+        for (kind, names) in [
+            ("Item", &pack.items),
+            ("Mission type", &pack.mission_types),
+            ("Challenge", &pack.challenges),
+        ] {
+            if let Some((key, _)) = names.iter().find(|(_, name)| name.trim().is_empty()) {
+                return Err(format!("{} {} has no name.", kind, key));
+            }
+        }
+
+        Ok(pack)
+    }
+
+    /// Returns true if the pack has no data, e.g: there is no pack.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+            && self.items.is_empty()
+            && self.mission_types.is_empty()
+            && self.challenges.is_empty()
+    }
+
+    /// Returns the pack the parsers use, it is loaded from the data directory on the first call.
+    pub fn current() -> Arc<DataPack> {
+        if let Some(pack) = DATA_PACK.read().as_ref() {
+            return pack.clone();
+        }
+
+        DATA_PACK
+            .write()
+            .get_or_insert_with(|| Arc::new(Self::load()))
+            .clone()
+    }
+
+    /// Download the pack from `url`, waiting at most `timeout` seconds,
+    /// store it in the data directory and use it from now on.
+    pub fn update(url: &str, timeout: u64) -> Result<Arc<DataPack>, String> {
+        let res = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(timeout))
+            .build()
+            .map_err(|e| e.to_string())?
+            .get(url)
+            .send()
+            .map_err(|e| e.to_string())?;
+        if !res.status().is_success() {
+            return Err(format!("HTTP {}", res.status()));
+        }
+
+        let mut pack = Self::parse(&res.text().map_err(|e| e.to_string())?)?;
+        pack.source = url.to_string();
+
+        fs::create_dir_all(data_path()).map_err(|e| e.to_string())?;
+        fs::write(
+            Self::path(),
+            serde_json::to_string(&pack).map_err(|e| e.to_string())?,
+        )
+        .map_err(|e| e.to_string())?;
+
+        let pack = Arc::new(pack);
+        // Build the node index again with the new nodes.
+        SolarNodes::reset_shared(|| *DATA_PACK.write() = Some(pack.clone()));
+
+        Ok(pack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_packs_parse() {
+        let pack = DataPack::parse(
+            r#"{
+                "version": "2022.10.19",
+                "nodes": { "SolNode999": { "value": "Zariman (Void)", "enemy": "Corrupted" } },
+                "items": { "/Lotus/Types/Items/MiscItems/Forma": "Forma" }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(pack.version, "2022.10.19");
+        assert_eq!(pack.nodes["SolNode999"].value, "Zariman (Void)");
+        assert_eq!(pack.items.len(), 1);
+        assert!(pack.mission_types.is_empty());
+    }
+
+    #[test]
+    fn packs_without_a_version_are_rejected() {
+        assert_eq!(
+            DataPack::parse(r#"{ "version": " ", "items": { "A": "B" } }"#),
+            Err("No version.".to_string())
+        );
+    }
+
+    #[test]
+    fn empty_packs_are_rejected() {
+        assert_eq!(
+            DataPack::parse(r#"{ "version": "1", "nodes": {}, "items": {} }"#),
+            Err("No data.".to_string())
+        );
+    }
+
+    #[test]
+    fn nodes_without_a_name_are_rejected() {
+        assert_eq!(
+            DataPack::parse(r#"{ "version": "1", "nodes": { "SolNode999": { "value": "" } } }"#),
+            Err("Node SolNode999 has no name.".to_string())
+        );
+    }
+
+    #[test]
+    fn items_without_a_name_are_rejected() {
+        assert_eq!(
+            DataPack::parse(r#"{ "version": "1", "items": { "/Lotus/Forma": "  " } }"#),
+            Err("Item /Lotus/Forma has no name.".to_string())
+        );
+        assert_eq!(
+            DataPack::parse(r#"{ "version": "1", "missionTypes": { "MT_CAPTURE": "" } }"#),
+            Err("Mission type MT_CAPTURE has no name.".to_string())
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use crate::parsers::{Fissure, Invasion};
+use crate::voidrat::TennoData;
+use parking_lot::Mutex;
+use std::fmt::{self, Display, Formatter};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Change between two states of the world.
+#[derive(Debug, Clone)]
+pub enum WorldEvent {
+    /// New normal fissure.
+    FissureAppeared(Fissure),
+    /// Fissure or void storm that has expired or is gone from the data.
+    FissureExpired(Fissure),
+    /// New void storm.
+    StormAppeared(Fissure),
+    InvasionAppeared(Invasion),
+    /// Invasion that is gone from the data.
+    InvasionCompleted(Invasion),
+    /// Cetus day or night started.
+    CycleChanged {
+        is_day: bool,
+    },
+}
+
+impl Display for WorldEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WorldEvent::FissureAppeared(fissure) => {
+                write!(
+                    f,
+                    "Fissure appeared: {} {}",
+                    fissure.tier, fissure.node.value
+                )
+            }
+            WorldEvent::FissureExpired(fissure) => {
+                write!(
+                    f,
+                    "Fissure expired: {} {}",
+                    fissure.tier, fissure.node.value
+                )
+            }
+            WorldEvent::StormAppeared(fissure) => {
+                write!(
+                    f,
+                    "Void storm appeared: {} {}",
+                    fissure.tier, fissure.node.value
+                )
+            }
+            WorldEvent::InvasionAppeared(invasion) => {
+                write!(f, "Invasion appeared: {}", invasion.node.value)
+            }
+            WorldEvent::InvasionCompleted(invasion) => {
+                write!(f, "Invasion completed: {}", invasion.node.value)
+            }
+            WorldEvent::CycleChanged { is_day: true } => f.write_str("Cetus day started"),
+            WorldEvent::CycleChanged { is_day: false } => f.write_str("Cetus night started"),
+        }
+    }
+}
+
+/// Remembers the previous state of the world and returns what has changed since.
+#[derive(Debug, Default)]
+pub struct WorldDiff {
+    /// Fissures that had not expired.
+    fissures: Vec<Fissure>,
+    invasions: Vec<Invasion>,
+    /// `None` before the first update.
+    is_day: Option<bool>,
+}
+
+impl WorldDiff {
+    /// Compare the `data` to the previous one and remember it for the next time.
+    ///
+    /// Everything in the first data counts as appeared.
+    /// The cycle only changes after the first data.
+    pub fn update(&mut self, data: &TennoData) -> Vec<WorldEvent> {
+        let mut events = vec![];
+
+        let fissures = data
+            .fissures
+            .iter()
+            .filter(|f| !f.has_expired())
+            .cloned()
+            .collect::<Vec<Fissure>>();
+
+        for old in self
+            .fissures
+            .iter()
+            .filter(|old| !fissures.iter().any(|f| same_fissure(f, old)))
+        {
+            events.push(WorldEvent::FissureExpired(old.clone()));
+        }
+        for new in fissures
+            .iter()
+            .filter(|new| !self.fissures.iter().any(|f| same_fissure(f, new)))
+        {
+            if new.is_storm {
+                events.push(WorldEvent::StormAppeared(new.clone()));
+            } else {
+                events.push(WorldEvent::FissureAppeared(new.clone()));
+            }
+        }
+
+        for old in self
+            .invasions
+            .iter()
+            .filter(|old| !data.invasions.iter().any(|i| same_invasion(i, old)))
+        {
+            events.push(WorldEvent::InvasionCompleted(old.clone()));
+        }
+        for new in data
+            .invasions
+            .iter()
+            .filter(|new| !self.invasions.iter().any(|i| same_invasion(i, new)))
+        {
+            events.push(WorldEvent::InvasionAppeared(new.clone()));
+        }
+
+        let is_day = data.cetus_cycle.cetus_is_day();
+        if self.is_day.is_some_and(|was_day| was_day != is_day) {
+            events.push(WorldEvent::CycleChanged { is_day });
+        }
+
+        self.fissures = fissures;
+        self.invasions = data.invasions.clone();
+        self.is_day = Some(is_day);
+
+        events
+    }
+}
+
+fn same_fissure(a: &Fissure, b: &Fissure) -> bool {
+    a.node.value == b.node.value
+        && a.tier == b.tier
+        && a.is_storm == b.is_storm
+        && a.hard == b.hard
+        && a.activation == b.activation
+}
+
+fn same_invasion(a: &Invasion, b: &Invasion) -> bool {
+    a.node.value == b.node.value && a.activation == b.activation
+}
+
+/// Sends the world events to every subscriber.
+#[derive(Debug, Default)]
+pub struct EventBus {
+    subscribers: Mutex<Vec<Sender<WorldEvent>>>,
+}
+
+impl EventBus {
+    /// Returns a channel that receives every event published from now on.
+    pub fn subscribe(&self) -> Receiver<WorldEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.lock().push(tx);
+
+        rx
+    }
+
+    /// Send the `events` to the subscribers.
+    /// Subscribers that have dropped their receiver are forgotten.
+    pub fn publish(&self, events: &[WorldEvent]) {
+        if events.is_empty() {
+            return;
+        }
+
+        self.subscribers
+            .lock()
+            .retain(|tx| events.iter().all(|e| tx.send(e.clone()).is_ok()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::FissureTier;
+    use chrono::{DateTime, Duration, Utc};
+
+    fn fissure(node: &str, is_storm: bool, activation: DateTime<Utc>) -> Fissure {
+        Fissure {
+            activation,
+            is_storm,
+            ..Fissure::test(node, FissureTier::Lith)
+        }
+    }
+
+    fn invasion(node: &str, activation: DateTime<Utc>) -> Invasion {
+        Invasion {
+            activation,
+            ..Invasion::test(node, "Forma Blueprint")
+        }
+    }
+
+    /// Cetus is in the day if the cycle ends in two hours, in the night if in ten minutes.
+    fn data(fissures: Vec<Fissure>, invasions: Vec<Invasion>, is_day: bool) -> TennoData {
+        let left = if is_day {
+            Duration::hours(2)
+        } else {
+            Duration::minutes(10)
+        };
+
+        TennoData::test(fissures, invasions, left)
+    }
+
+    #[test]
+    fn everything_appears_on_the_first_update() {
+        let start = Utc::now();
+        let mut diff = WorldDiff::default();
+
+        let events = diff.update(&data(
+            vec![
+                fissure("Hepit (Void)", false, start),
+                fissure("Ani (Void)", true, start),
+            ],
+            vec![invasion("Kiliken (Venus)", start)],
+            true,
+        ));
+
+        assert_eq!(events.len(), 3);
+        assert!(
+            matches!(&events[0], WorldEvent::FissureAppeared(f) if f.node.value == "Hepit (Void)")
+        );
+        assert!(matches!(&events[1], WorldEvent::StormAppeared(f) if f.node.value == "Ani (Void)"));
+        assert!(
+            matches!(&events[2], WorldEvent::InvasionAppeared(i) if i.node.value == "Kiliken (Venus)")
+        );
+    }
+
+    #[test]
+    fn nothing_changes_for_the_same_data() {
+        let start = Utc::now();
+        let world = data(
+            vec![fissure("Hepit (Void)", false, start)],
+            vec![invasion("Kiliken (Venus)", start)],
+            true,
+        );
+        let mut diff = WorldDiff::default();
+
+        diff.update(&world);
+
+        assert!(diff.update(&world).is_empty());
+    }
+
+    #[test]
+    fn gone_and_new_items_are_reported() {
+        let start = Utc::now();
+        let mut diff = WorldDiff::default();
+        diff.update(&data(
+            vec![fissure("Hepit (Void)", false, start)],
+            vec![invasion("Kiliken (Venus)", start)],
+            true,
+        ));
+
+        // The same node with another activation is another fissure.
+        let later = start + Duration::minutes(5);
+        let events = diff.update(&data(
+            vec![fissure("Hepit (Void)", false, later)],
+            vec![],
+            true,
+        ));
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], WorldEvent::FissureExpired(f) if f.activation == start));
+        assert!(matches!(&events[1], WorldEvent::FissureAppeared(f) if f.activation == later));
+        assert!(matches!(&events[2], WorldEvent::InvasionCompleted(_)));
+    }
+
+    #[test]
+    fn expired_fissures_count_as_gone() {
+        let start = Utc::now();
+        let mut diff = WorldDiff::default();
+        let mut hepit = fissure("Hepit (Void)", false, start);
+        diff.update(&data(vec![hepit.clone()], vec![], true));
+
+        hepit.expiry = start - Duration::minutes(1);
+        let events = diff.update(&data(vec![hepit], vec![], true));
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], WorldEvent::FissureExpired(_)));
+    }
+
+    #[test]
+    fn cycle_changes_only_after_the_first_update() {
+        let mut diff = WorldDiff::default();
+
+        assert!(diff.update(&data(vec![], vec![], true)).is_empty());
+        assert!(diff.update(&data(vec![], vec![], true)).is_empty());
+
+        let events = diff.update(&data(vec![], vec![], false));
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            WorldEvent::CycleChanged { is_day: false }
+        ));
+    }
+
+    #[test]
+    fn every_subscriber_gets_the_events() {
+        let bus = EventBus::default();
+        let first = bus.subscribe();
+        let second = bus.subscribe();
+
+        bus.publish(&[WorldEvent::CycleChanged { is_day: true }]);
+
+        assert_eq!(first.try_iter().count(), 1);
+        assert_eq!(second.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn dropped_subscribers_are_forgotten() {
+        let bus = EventBus::default();
+        let kept = bus.subscribe();
+        drop(bus.subscribe());
+
+        bus.publish(&[WorldEvent::CycleChanged { is_day: true }]);
+
+        assert_eq!(bus.subscribers.lock().len(), 1);
+        assert_eq!(kept.try_iter().count(), 1);
+    }
+}
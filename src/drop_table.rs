@@ -0,0 +1,321 @@
+use crate::parsers::FissureTier;
+use crate::util::Resources;
+use crate::voidrat::data_path;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DROP_TABLE_FILE: &str = "relic_drops.json";
+/// Source of the bundled table, only a few relics to start with.
+const SAMPLE_SOURCE: &str = "sample";
+/// Full relic drop table, same format as the bundled one minus the vaulted status.
+pub const DROP_TABLE_URL: &str = "https://drops.warframestat.us/data/relics.json";
+/// Every relic item with its vaulted status, e.g: `{"name": "Axi A1 Relic", "vaulted": true}`.
+pub const VAULTED_URL: &str =
+    "https://raw.githubusercontent.com/WFCD/warframe-items/master/data/json/Relics.json";
+/// Suffixes of the relic item names, e.g: `Axi A1 Radiant`.
+const RELIC_SUFFIXES: [&str; 5] = [" Relic", " Intact", " Exceptional", " Flawless", " Radiant"];
+
+/// Single reward from a relic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelicReward {
+    pub item_name: String,
+    /// Common, Uncommon or Rare.
+    pub rarity: String,
+    /// Drop chance in percents for an intact relic.
+    pub chance: f32,
+}
+
+/// Rewards of a single relic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelicDrops {
+    /// Relic tier in string, e.g: `Lith`.
+    pub tier: String,
+    /// Relic name without the tier, e.g: `A1`.
+    pub relic_name: String,
+    /// Refinement the chances are for.
+    #[serde(default = "intact")]
+    pub state: String,
+    pub rewards: Vec<RelicReward>,
+    /// True if the relic no longer drops, `None` when unknown.
+    #[serde(default)]
+    pub vaulted: Option<bool>,
+}
+
+fn intact() -> String {
+    "Intact".to_string()
+}
+
+impl RelicDrops {
+    pub fn tier(&self) -> FissureTier {
+        FissureTier::from_str(&self.tier)
+    }
+
+    /// Full relic name, e.g: `Lith A1`.
+    pub fn full_name(&self) -> String {
+        format!("{} {}", self.tier, self.relic_name)
+    }
+
+    /// Human readable vaulted status.
+    pub fn vaulted_text(&self) -> &str {
+        match self.vaulted {
+            Some(true) => "Vaulted",
+            Some(false) => "Unvaulted",
+            None => "",
+        }
+    }
+}
+
+/// Relic rewards for all the known relics.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DropTable {
+    /// Where the data came from.
+    #[serde(default)]
+    pub source: String,
+    pub relics: Vec<RelicDrops>,
+}
+
+impl DropTable {
+    fn override_path() -> PathBuf {
+        data_path().join(DROP_TABLE_FILE)
+    }
+
+    /// Load the drop table from the data directory if there is one,
+    /// otherwise use the bundled one.
+    pub fn load() -> Self {
+        if let Ok(json) = fs::read_to_string(Self::override_path()) {
+            match Self::parse(&json) {
+                Ok(table) => return table,
+                Err(e) => warn!("Cannot parse {}: {}", DROP_TABLE_FILE, e),
+            }
+        }
+
+        let data = Resources::get(&format!("data/{}", DROP_TABLE_FILE))
+            .unwrap()
+            .data;
+
+        Self::parse(&String::from_utf8_lossy(&data)).expect("Bad JSON.")
+    }
+
+    /// Returns true if this is the bundled sample and not the full table.
+    pub fn is_sample(&self) -> bool {
+        self.source == SAMPLE_SOURCE
+    }
+
+    /// Parse and validate the drop table.
+    /// Only intact relic chances are kept.
+    fn parse(json: &str) -> Result<Self, String> {
+        let mut table: DropTable = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+        table
+            .relics
+            .retain(|r| r.state == "Intact" && r.tier() != FissureTier::Unknown);
+
+        if table.relics.is_empty() {
+            return Err("No relics found.".to_string());
+        }
+
+        table.relics.sort_by(|a, b| {
+            a.tier()
+                .cmp(&b.tier())
+                .then_with(|| a.relic_name.cmp(&b.relic_name))
+        });
+
+        Ok(table)
+    }
+
+    /// Download the drop table from `url` and the vaulted statuses from `vaulted_url`,
+    /// waiting at most `timeout` seconds for each, and store the table in the data directory.
+    /// The current vaulted statuses are kept for relics the vaulted list does not know about,
+    /// or for all of them if the list cannot be downloaded.
+    pub fn update(&mut self, url: &str, vaulted_url: &str, timeout: u64) -> Result<usize, String> {
+        let mut table = Self::parse(&fetch(url, timeout)?)?;
+        table.source = url.to_string();
+
+        let vaulted = fetch(vaulted_url, timeout)
+            .and_then(|json| parse_vaulted(&json))
+            .unwrap_or_else(|e| {
+                warn!("Cannot get the vaulted relics: {}", e);
+                HashMap::new()
+            });
+        table.merge_vaulted(&vaulted, self);
+
+        fs::create_dir_all(data_path()).map_err(|e| e.to_string())?;
+        fs::write(
+            Self::override_path(),
+            serde_json::to_string(&table).map_err(|e| e.to_string())?,
+        )
+        .map_err(|e| e.to_string())?;
+
+        *self = table;
+
+        Ok(self.relics.len())
+    }
+
+    /// Fill in the vaulted statuses from `vaulted`, keyed by the full relic name,
+    /// or else from the same relic in the `previous` table.
+    fn merge_vaulted(&mut self, vaulted: &HashMap<String, bool>, previous: &DropTable) {
+        for relic in self.relics.iter_mut().filter(|r| r.vaulted.is_none()) {
+            relic.vaulted = vaulted.get(&relic.full_name()).copied().or_else(|| {
+                previous
+                    .relics
+                    .iter()
+                    .find(|r| r.tier == relic.tier && r.relic_name == relic.relic_name)
+                    .and_then(|r| r.vaulted)
+            });
+        }
+    }
+
+    /// Returns the relics of the `tier`.
+    pub fn relics_for_tier<'a>(
+        &'a self,
+        tier: &'a FissureTier,
+    ) -> impl Iterator<Item = &'a RelicDrops> + 'a {
+        self.relics.iter().filter(move |r| r.tier() == *tier)
+    }
+
+    /// Returns every relic and reward where the reward name contains the `query`, case insensitive.
+    pub fn search(&self, query: &str) -> Vec<(&RelicDrops, &RelicReward)> {
+        let query = query.trim().to_lowercase();
+
+        self.relics
+            .iter()
+            .flat_map(|relic| relic.rewards.iter().map(move |reward| (relic, reward)))
+            .filter(|(_, reward)| reward.item_name.to_lowercase().contains(&query))
+            .collect()
+    }
+}
+
+/// Returns the body of the `url`, waiting at most `timeout` seconds.
+fn fetch(url: &str, timeout: u64) -> Result<String, String> {
+    let res = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(timeout))
+        .build()
+        .map_err(|e| e.to_string())?
+        .get(url)
+        .send()
+        .map_err(|e| e.to_string())?;
+    if !res.status().is_success() {
+        return Err(format!("HTTP {}", res.status()));
+    }
+
+    res.text().map_err(|e| e.to_string())
+}
+
+/// Relic item with its vaulted status.
+#[derive(Debug, Deserialize)]
+struct RelicItem {
+    name: String,
+    #[serde(default)]
+    vaulted: Option<bool>,
+}
+
+/// Parse a list of relic items to the vaulted status by the full relic name, e.g: `Axi A1`.
+/// A relic is vaulted if any of its refinements is.
+fn parse_vaulted(json: &str) -> Result<HashMap<String, bool>, String> {
+    let items: Vec<RelicItem> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let mut vaulted = HashMap::new();
+
+    for item in items {
+        let (Some(is_vaulted), Some(name)) = (
+            item.vaulted,
+            RELIC_SUFFIXES
+                .iter()
+                .find_map(|s| item.name.strip_suffix(s)),
+        ) else {
+            continue;
+        };
+
+        *vaulted.entry(name.to_string()).or_insert(false) |= is_vaulted;
+    }
+
+    if vaulted.is_empty() {
+        return Err("No relics found.".to_string());
+    }
+
+    Ok(vaulted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relic(tier: &str, name: &str, vaulted: Option<bool>) -> RelicDrops {
+        RelicDrops {
+            tier: tier.to_string(),
+            relic_name: name.to_string(),
+            state: intact(),
+            rewards: vec![],
+            vaulted,
+        }
+    }
+
+    #[test]
+    fn vaulted_statuses_come_from_the_relic_items() {
+        let vaulted = parse_vaulted(
+            r#"[
+                {"name": "Axi A1 Relic", "vaulted": true},
+                {"name": "Lith B2 Intact", "vaulted": false},
+                {"name": "Lith B2 Radiant", "vaulted": false},
+                {"name": "Meso C3 Intact", "vaulted": false},
+                {"name": "Meso C3 Radiant", "vaulted": true},
+                {"name": "Neo D4 Relic"},
+                {"name": "Forma Blueprint", "vaulted": true}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(vaulted.len(), 3);
+        assert!(vaulted["Axi A1"]);
+        assert!(!vaulted["Lith B2"]);
+        assert!(vaulted["Meso C3"]);
+    }
+
+    #[test]
+    fn lists_without_relics_are_rejected() {
+        assert!(parse_vaulted(r#"[{"name": "Forma Blueprint"}]"#).is_err());
+        assert!(parse_vaulted("{}").is_err());
+    }
+
+    #[test]
+    fn merging_prefers_the_vaulted_list_then_the_previous_table() {
+        let previous = DropTable {
+            source: SAMPLE_SOURCE.to_string(),
+            relics: vec![
+                relic("Axi", "A1", Some(false)),
+                relic("Neo", "D4", Some(true)),
+            ],
+        };
+        let mut table = DropTable {
+            source: DROP_TABLE_URL.to_string(),
+            relics: vec![
+                relic("Axi", "A1", None),
+                relic("Lith", "B2", None),
+                relic("Neo", "D4", None),
+                relic("Requiem", "I", None),
+            ],
+        };
+        let vaulted = HashMap::from([("Axi A1".to_string(), true), ("Lith B2".to_string(), false)]);
+
+        table.merge_vaulted(&vaulted, &previous);
+
+        let statuses = table.relics.iter().map(|r| r.vaulted).collect::<Vec<_>>();
+        assert_eq!(statuses, [Some(true), Some(false), Some(true), None]);
+    }
+
+    #[test]
+    fn the_bundled_table_is_the_sample() {
+        let data = Resources::get(&format!("data/{}", DROP_TABLE_FILE))
+            .unwrap()
+            .data;
+        let table = DropTable::parse(&String::from_utf8_lossy(&data)).unwrap();
+
+        assert!(table.is_sample());
+        assert!(table.relics.iter().all(|r| r.vaulted.is_some()));
+    }
+}
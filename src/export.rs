@@ -0,0 +1,397 @@
+use crate::archive::join_rewards;
+use crate::clock;
+use crate::parsers::{CetusCycle, Fissure, Invasion};
+use crate::util::csv_line;
+use crate::voidrat::{data_path, TennoData};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use serde_json::{json, Value};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+
+const EXPORT_DIR: &str = "export";
+/// Length of a whole Cetus day and night cycle in seconds.
+const CETUS_CYCLE: i64 = 9000;
+/// Length of the Cetus night in seconds.
+const CETUS_NIGHT: i64 = 3000;
+/// How many Cetus cycles are exported, roughly a day worth.
+const CETUS_CYCLES: i64 = 10;
+
+/// File formats the current data can be exported as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    /// iCalendar with fissure expiries, Cetus nights and weekly resets.
+    Ics,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Csv, ExportFormat::Json, ExportFormat::Ics];
+
+    fn extension(&self) -> &str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ics => "ics",
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Ics => "Calendar (.ics)",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Single timed event, e.g: a Cetus night.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedEvent {
+    pub name: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Returns the upcoming Cetus days and nights, starting from the current cycle.
+pub fn cetus_windows(cycle: &CetusCycle) -> Vec<TimedEvent> {
+    let mut events = vec![];
+
+    for i in 0..CETUS_CYCLES {
+        let expiry = cycle.expiry + Duration::seconds(i * CETUS_CYCLE);
+        let night_start = expiry - Duration::seconds(CETUS_NIGHT);
+
+        events.push(TimedEvent {
+            name: "Cetus day".to_string(),
+            start: expiry - Duration::seconds(CETUS_CYCLE),
+            end: night_start,
+        });
+        events.push(TimedEvent {
+            name: "Cetus night".to_string(),
+            start: night_start,
+            end: expiry,
+        });
+    }
+
+    events
+}
+
+/// Returns the next weekly reset, Monday 00:00 UTC.
+pub fn next_weekly_reset(now: DateTime<Utc>) -> DateTime<Utc> {
+    let today = now.naive_utc().date();
+    let days = 7 - today.weekday().num_days_from_monday() as i64;
+
+    Utc.from_utc_datetime(&(today + Duration::days(days)).and_hms_opt(0, 0, 0).unwrap())
+}
+
+/// Returns the Cetus windows and the next weekly reset.
+pub fn cycle_timings(cycle: &CetusCycle) -> Vec<TimedEvent> {
+    let mut events = cetus_windows(cycle);
+
+    let reset = next_weekly_reset(clock::now());
+    events.push(TimedEvent {
+        name: "Weekly reset".to_string(),
+        start: reset,
+        end: reset,
+    });
+
+    events
+}
+
+pub fn fissures_csv(fissures: &[&Fissure]) -> String {
+    let mut lines = vec![csv_line(&[
+        "tier".into(),
+        "mission".into(),
+        "node".into(),
+        "void_storm".into(),
+        "steel_path".into(),
+        "activation".into(),
+        "expiry".into(),
+    ])];
+
+    for f in fissures {
+        lines.push(csv_line(&[
+            f.tier.to_string(),
+            f.mission.clone(),
+            f.node.value.clone(),
+            f.is_storm.to_string(),
+            f.hard.to_string(),
+            f.activation.to_rfc3339(),
+            f.expiry.to_rfc3339(),
+        ]));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+pub fn fissures_json(fissures: &[&Fissure]) -> Value {
+    fissures
+        .iter()
+        .map(|f| {
+            json!({
+                "tier": f.tier,
+                "mission": f.mission,
+                "node": f.node.value,
+                "voidStorm": f.is_storm,
+                "steelPath": f.hard,
+                "activation": f.activation.to_rfc3339(),
+                "expiry": f.expiry.to_rfc3339(),
+            })
+        })
+        .collect()
+}
+
+pub fn invasions_csv(invasions: &[Invasion]) -> String {
+    let mut lines = vec![csv_line(&[
+        "node".into(),
+        "attacker_rewards".into(),
+        "defender_rewards".into(),
+        "activation".into(),
+    ])];
+
+    for i in invasions {
+        lines.push(csv_line(&[
+            i.node.value.clone(),
+            join_rewards(&i.rewards.attacker),
+            join_rewards(&i.rewards.defender),
+            i.activation.to_rfc3339(),
+        ]));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+pub fn invasions_json(invasions: &[Invasion]) -> Value {
+    invasions
+        .iter()
+        .map(|i| {
+            json!({
+                "node": i.node.value,
+                "attackerRewards": i.rewards.attacker.iter().map(|r| r.to_string()).collect::<Vec<String>>(),
+                "defenderRewards": i.rewards.defender.iter().map(|r| r.to_string()).collect::<Vec<String>>(),
+                "activation": i.activation.to_rfc3339(),
+            })
+        })
+        .collect()
+}
+
+pub fn cycles_csv(events: &[TimedEvent]) -> String {
+    let mut lines = vec![csv_line(&["event".into(), "start".into(), "end".into()])];
+
+    for e in events {
+        lines.push(csv_line(&[
+            e.name.clone(),
+            e.start.to_rfc3339(),
+            e.end.to_rfc3339(),
+        ]));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+pub fn cycles_json(events: &[TimedEvent]) -> Value {
+    events
+        .iter()
+        .map(|e| {
+            json!({
+                "event": e.name,
+                "start": e.start.to_rfc3339(),
+                "end": e.end.to_rfc3339(),
+            })
+        })
+        .collect()
+}
+
+/// Returns an iCalendar with events for fissure expiries, Cetus nights and weekly resets.
+/// The weekly reset repeats every week.
+pub fn calendar(fissures: &[&Fissure], cycle: &CetusCycle) -> String {
+    let now = clock::now();
+    let stamp = ics_time(now);
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//voidrat//voidrat//EN".to_string(),
+        "X-WR-CALNAME:Voidrat".to_string(),
+    ];
+
+    let mut event = |uid: String, summary: String, start: DateTime<Utc>, end: DateTime<Utc>| {
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}@voidrat", ics_escape(&uid)),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART:{}", ics_time(start)),
+            format!("DTEND:{}", ics_time(end)),
+            format!("SUMMARY:{}", ics_escape(&summary)),
+            "END:VEVENT".to_string(),
+        ]);
+    };
+
+    for f in fissures {
+        let kind = match (f.is_storm, f.hard) {
+            (true, _) => " Void Storm",
+            (_, true) => " Steel Path",
+            _ => "",
+        };
+        event(
+            format!(
+                "fissure-{}-{}-{}-{}",
+                f.tier,
+                f.node.value,
+                kind.trim(),
+                f.activation.timestamp()
+            ),
+            format!(
+                "{} {}{} expires ({})",
+                f.tier, f.mission, kind, f.node.value
+            ),
+            f.expiry,
+            f.expiry,
+        );
+    }
+
+    for night in cetus_windows(cycle)
+        .into_iter()
+        .filter(|e| e.name == "Cetus night" && e.end > now)
+    {
+        event(
+            format!("cetus-night-{}", night.start.timestamp()),
+            night.name,
+            night.start,
+            night.end,
+        );
+    }
+
+    let reset = next_weekly_reset(now);
+    lines.extend([
+        "BEGIN:VEVENT".to_string(),
+        "UID:weekly-reset@voidrat".to_string(),
+        format!("DTSTAMP:{}", stamp),
+        format!("DTSTART:{}", ics_time(reset)),
+        format!("DTEND:{}", ics_time(reset)),
+        "RRULE:FREQ=WEEKLY;BYDAY=MO".to_string(),
+        "SUMMARY:Weekly reset".to_string(),
+        "END:VEVENT".to_string(),
+        "END:VCALENDAR".to_string(),
+    ]);
+
+    // iCalendar lines end with CRLF.
+    lines.join("\r\n") + "\r\n"
+}
+
+/// Write the filtered fissures, invasions and cycle timings in the `format`
+/// to the export directory inside the data directory.
+/// Returns the written files.
+pub fn export(data: &TennoData, format: ExportFormat) -> std::io::Result<Vec<PathBuf>> {
+    let dir = data_path().join(EXPORT_DIR);
+    fs::create_dir_all(&dir)?;
+
+    let fissures = data.filtered_fissures();
+    let cycles = cycle_timings(&data.cetus_cycle);
+
+    let files = match format {
+        ExportFormat::Csv => vec![
+            ("fissures", fissures_csv(&fissures)),
+            ("invasions", invasions_csv(&data.invasions)),
+            ("cycles", cycles_csv(&cycles)),
+        ],
+        ExportFormat::Json => vec![
+            ("fissures", pretty(&fissures_json(&fissures))),
+            ("invasions", pretty(&invasions_json(&data.invasions))),
+            ("cycles", pretty(&cycles_json(&cycles))),
+        ],
+        ExportFormat::Ics => vec![("voidrat", calendar(&fissures, &data.cetus_cycle))],
+    };
+
+    let mut paths = vec![];
+    for (name, content) in files {
+        let path = dir.join(format!("{}.{}", name, format.extension()));
+        fs::write(&path, content)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).expect("Cannot serialize JSON.")
+}
+
+/// Time in the iCalendar UTC format, e.g: `20221001T120000Z`.
+fn ics_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape the iCalendar text special characters.
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::FissureTier;
+
+    fn utc(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.ymd(2022, 10, day).and_hms(hour, minute, 0)
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(
+            ics_escape("Lith; Capture, Hepit"),
+            r"Lith\; Capture\, Hepit"
+        );
+        assert_eq!(ics_escape("a\\b\nc"), r"a\\b\nc");
+        assert_eq!(ics_escape("Hepit (Void)"), "Hepit (Void)");
+    }
+
+    #[test]
+    fn weekly_reset_is_the_next_monday() {
+        // 2022-10-03 is a Monday and 2022-10-09 a Sunday.
+        assert_eq!(next_weekly_reset(utc(3, 0, 0)), utc(10, 0, 0));
+        assert_eq!(next_weekly_reset(utc(3, 12, 30)), utc(10, 0, 0));
+        assert_eq!(next_weekly_reset(utc(9, 0, 0)), utc(10, 0, 0));
+        assert_eq!(next_weekly_reset(utc(9, 23, 59)), utc(10, 0, 0));
+        assert_eq!(next_weekly_reset(utc(6, 8, 0)), utc(10, 0, 0));
+    }
+
+    #[test]
+    fn calendar_has_crlf_lines_and_one_event_per_item() {
+        let mut hepit = Fissure::test("Hepit (Void)", FissureTier::Lith);
+        hepit.mission = "Capture, Extra".to_string();
+        let cycle = CetusCycle {
+            expiry: Utc::now() + Duration::minutes(10),
+        };
+
+        let ics = calendar(&[&hepit], &cycle);
+
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        let lines = ics
+            .trim_end_matches("\r\n")
+            .split("\r\n")
+            .collect::<Vec<&str>>();
+        assert!(lines.iter().all(|l| !l.contains('\n') && !l.contains('\r')));
+        assert_eq!(lines[0], "BEGIN:VCALENDAR");
+        assert_eq!(lines[1], "VERSION:2.0");
+
+        let begins = lines.iter().filter(|l| **l == "BEGIN:VEVENT").count();
+        let ends = lines.iter().filter(|l| **l == "END:VEVENT").count();
+        // The fissure, every upcoming night and the weekly reset.
+        assert_eq!(begins, 1 + CETUS_CYCLES as usize + 1);
+        assert_eq!(begins, ends);
+
+        assert!(lines.contains(&r"SUMMARY:Lith Capture\, Extra expires (Hepit (Void))"));
+        assert!(lines.contains(&"RRULE:FREQ=WEEKLY;BYDAY=MO"));
+        assert!(lines
+            .iter()
+            .filter(|l| l.starts_with("DTSTART:"))
+            .all(|l| l.len() == "DTSTART:20221001T120000Z".len() && l.ends_with('Z')));
+    }
+}
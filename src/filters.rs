@@ -0,0 +1,214 @@
+use crate::data_pack::DataPack;
+use crate::parsers::world_state::MissionType;
+use crate::parsers::{Fissure, FissureTier};
+
+/// Which fissures are shown.
+///
+/// Values are stored as strings so new mission types, planets and factions
+/// do not change the storage layout. Anything not hidden is shown.
+#[derive(Debug, Clone, Default, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct FissureFilter {
+    /// Hidden mission types, e.g: `Capture`.
+    pub hidden_missions: Vec<String>,
+    /// Hidden tiers, e.g: `Lith`.
+    pub hidden_tiers: Vec<String>,
+    /// Hidden planets, e.g: `Void`.
+    pub hidden_planets: Vec<String>,
+    /// Hidden factions, e.g: `Orokin`.
+    pub hidden_factions: Vec<String>,
+    /// `Some(true)` shows only Steel Path fissures, `Some(false)` hides them.
+    pub steel_path: Option<bool>,
+    /// `Some(true)` shows only void storms, `Some(false)` hides them.
+    pub storms: Option<bool>,
+}
+
+impl FissureFilter {
+    /// Returns true if the filter lets the fissure through.
+    pub fn shows(&self, fissure: &Fissure) -> bool {
+        !self.hidden_missions.contains(&fissure.mission)
+            && !self.hidden_tiers.contains(&fissure.tier.to_string())
+            && !fissure
+                .node
+                .planet
+                .as_ref()
+                .is_some_and(|p| self.hidden_planets.contains(p))
+            && !fissure
+                .node
+                .enemy
+                .as_ref()
+                .is_some_and(|e| self.hidden_factions.contains(e))
+            && self.steel_path.is_none_or(|hard| hard == fissure.hard)
+            && self.storms.is_none_or(|storm| storm == fissure.is_storm)
+    }
+}
+
+/// Show or hide the `value` by adding or removing it from the `hidden` values.
+pub fn set_shown(hidden: &mut Vec<String>, value: &str, shown: bool) {
+    hidden.retain(|h| h != value);
+
+    if !shown {
+        hidden.push(value.to_string());
+    }
+}
+
+/// Named filter the user has saved.
+#[derive(Debug, Clone, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct FilterPreset {
+    pub name: String,
+    pub filter: FissureFilter,
+}
+
+/// Values the filters can be set for, based on the known mission types and the fissures seen.
+#[derive(Debug, Clone, Default)]
+pub struct FilterOptions {
+    pub missions: Vec<String>,
+    pub tiers: Vec<String>,
+    pub planets: Vec<String>,
+    pub factions: Vec<String>,
+}
+
+impl FilterOptions {
+    /// Collect the options from the `fissures` and the `filter`, so hidden values can be shown again.
+    pub fn new(fissures: &[Fissure], filter: &FissureFilter) -> Self {
+        let mut missions = MissionType::ALL
+            .iter()
+            .map(|m| m.to_string())
+            .chain(DataPack::current().mission_types.values().cloned())
+            .chain(fissures.iter().map(|f| f.mission.clone()))
+            .chain(filter.hidden_missions.iter().cloned())
+            .collect::<Vec<String>>();
+
+        let tiers = FissureTier::ALL
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<String>>();
+
+        let mut planets = fissures
+            .iter()
+            .filter_map(|f| f.node.planet.clone())
+            .chain(filter.hidden_planets.iter().cloned())
+            .collect::<Vec<String>>();
+
+        let mut factions = fissures
+            .iter()
+            .filter_map(|f| f.node.enemy.clone())
+            .chain(filter.hidden_factions.iter().cloned())
+            .collect::<Vec<String>>();
+
+        for list in [&mut missions, &mut planets, &mut factions] {
+            list.sort();
+            list.dedup();
+        }
+
+        FilterOptions {
+            missions,
+            tiers,
+            planets,
+            factions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lith Capture fissure in the Void against the Corrupted.
+    fn hepit() -> Fissure {
+        let mut hepit = Fissure::test("Hepit (Void)", FissureTier::Lith);
+        hepit.node.enemy = Some("Corrupted".to_string());
+
+        hepit
+    }
+
+    #[test]
+    fn the_default_filter_shows_everything() {
+        let hepit = hepit();
+
+        assert!(FissureFilter::default().shows(&hepit));
+    }
+
+    #[test]
+    fn hidden_values_hide_the_fissure() {
+        let hepit = hepit();
+
+        for filter in [
+            FissureFilter {
+                hidden_missions: vec!["Capture".to_string()],
+                ..Default::default()
+            },
+            FissureFilter {
+                hidden_tiers: vec!["Lith".to_string()],
+                ..Default::default()
+            },
+            FissureFilter {
+                hidden_planets: vec!["Void".to_string()],
+                ..Default::default()
+            },
+            FissureFilter {
+                hidden_factions: vec!["Corrupted".to_string()],
+                ..Default::default()
+            },
+        ] {
+            assert!(!filter.shows(&hepit), "{:?}", filter);
+        }
+    }
+
+    #[test]
+    fn steel_path_and_storms_show_only_or_hide() {
+        let normal = hepit();
+        let hard = Fissure {
+            hard: true,
+            ..normal.clone()
+        };
+        let storm = Fissure {
+            is_storm: true,
+            ..normal.clone()
+        };
+
+        let only_hard = FissureFilter {
+            steel_path: Some(true),
+            ..Default::default()
+        };
+        assert!(only_hard.shows(&hard));
+        assert!(!only_hard.shows(&normal));
+
+        let no_storms = FissureFilter {
+            storms: Some(false),
+            ..Default::default()
+        };
+        assert!(no_storms.shows(&normal));
+        assert!(!no_storms.shows(&storm));
+    }
+
+    #[test]
+    fn set_shown_adds_and_removes_once() {
+        let mut hidden = vec![];
+
+        set_shown(&mut hidden, "Void", false);
+        set_shown(&mut hidden, "Void", false);
+        assert_eq!(hidden, ["Void"]);
+
+        set_shown(&mut hidden, "Void", true);
+        assert!(hidden.is_empty());
+    }
+
+    #[test]
+    fn options_keep_the_hidden_values() {
+        let mut ukko = Fissure::test("Ukko (Void)", FissureTier::Axi);
+        ukko.mission = "Survival".to_string();
+        ukko.node.enemy = Some("Grineer".to_string());
+        let fissures = [hepit(), ukko];
+        let filter = FissureFilter {
+            hidden_planets: vec!["Ceres".to_string()],
+            ..Default::default()
+        };
+
+        let options = FilterOptions::new(&fissures, &filter);
+
+        assert_eq!(options.planets, ["Ceres", "Void"]);
+        assert_eq!(options.factions, ["Corrupted", "Grineer"]);
+        assert_eq!(options.tiers.len(), FissureTier::ALL.len());
+        assert!(options.missions.contains(&"Survival".to_string()));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::parsers::{Reward, SolarNode};
+use crate::util::Resources;
+use log::warn;
+use parking_lot::RwLock;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+/// Language of the UI and the game names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Portuguese,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::German, Language::Portuguese];
+
+    /// ISO 639-1 code of the language, used in the catalog file names.
+    pub fn code(&self) -> &str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::Portuguese => "pt",
+        }
+    }
+}
+
+impl Display for Language {
+    /// Name of the language in the language itself.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::Portuguese => "Português",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Game names of one language, keyed by the English name.
+/// Names the game leaves in English, like most nodes and the relic tiers, are not listed.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct Names {
+    /// Node name without the planet, e.g: `Hepit`.
+    #[serde(default)]
+    nodes: HashMap<String, String>,
+    #[serde(default)]
+    planets: HashMap<String, String>,
+    #[serde(default)]
+    missions: HashMap<String, String>,
+    #[serde(default)]
+    items: HashMap<String, String>,
+    #[serde(default)]
+    factions: HashMap<String, String>,
+    #[serde(default)]
+    tiers: HashMap<String, String>,
+}
+
+/// UI strings and game names of one language.
+#[derive(Debug, Clone, Default)]
+struct Catalog {
+    /// English UI string to the translation.
+    ui: HashMap<String, String>,
+    names: Names,
+}
+
+impl Catalog {
+    /// Load the bundled `i18n/<code>.json` and `data/names_<code>.json`.
+    /// English has no catalog, missing files and strings stay in English.
+    fn load(language: Language) -> Self {
+        if language == Language::English {
+            return Self::default();
+        }
+
+        Catalog {
+            ui: load_json(&format!("i18n/{}.json", language.code())),
+            names: load_json(&format!("data/names_{}.json", language.code())),
+        }
+    }
+}
+
+fn load_json<T: Default + for<'de> Deserialize<'de>>(file: &str) -> T {
+    let data = match Resources::get(file) {
+        Some(file) => file.data,
+        None => return T::default(),
+    };
+
+    serde_json::from_slice(&data).unwrap_or_else(|e| {
+        warn!("Cannot parse {}: {}", file, e);
+        T::default()
+    })
+}
+
+/// Language in use and its catalog.
+static CATALOG: RwLock<Option<(Language, Arc<Catalog>)>> = RwLock::new(None);
+
+/// Use the `language` everywhere from now on.
+pub fn set_language(language: Language) {
+    *CATALOG.write() = Some((language, Arc::new(Catalog::load(language))));
+}
+
+/// Returns the language in use.
+pub fn language() -> Language {
+    CATALOG
+        .read()
+        .as_ref()
+        .map_or(Language::English, |(language, _)| *language)
+}
+
+fn catalog() -> Option<Arc<Catalog>> {
+    CATALOG.read().as_ref().map(|(_, catalog)| catalog.clone())
+}
+
+/// Look up the `text` from the catalog with `get`, the `text` itself if there is no translation.
+fn lookup(text: &str, get: impl Fn(&Catalog) -> &HashMap<String, String>) -> String {
+    catalog()
+        .and_then(|c| get(&c).get(text).cloned())
+        .unwrap_or_else(|| text.to_string())
+}
+
+/// Translate the UI string.
+pub fn tr(text: &str) -> String {
+    lookup(text, |c| &c.ui)
+}
+
+/// Translate the mission type, e.g: `Capture`.
+pub fn mission(name: &str) -> String {
+    lookup(name, |c| &c.names.missions)
+}
+
+/// Translate the item name, e.g: `Forma Blueprint`.
+pub fn item(name: &str) -> String {
+    lookup(name, |c| &c.names.items)
+}
+
+/// Translate the planet, e.g: `Earth`.
+pub fn planet(name: &str) -> String {
+    lookup(name, |c| &c.names.planets)
+}
+
+/// Translate the faction, e.g: `Infested`.
+pub fn faction(name: &str) -> String {
+    lookup(name, |c| &c.names.factions)
+}
+
+/// Translate the fissure tier, e.g: `Lith`.
+pub fn tier(name: &str) -> String {
+    lookup(name, |c| &c.names.tiers)
+}
+
+/// Translated node name with the planet, e.g: `Hepit (Void)`.
+pub fn node(node: &SolarNode) -> String {
+    match &node.planet {
+        Some(p) => format!("{} ({})", lookup(&node.name, |c| &c.names.nodes), planet(p)),
+        None => lookup(&node.value, |c| &c.names.nodes),
+    }
+}
+
+/// Translated reward with the quantity, e.g: `3 Mutagen Mass`.
+pub fn reward(reward: &Reward) -> String {
+    if reward.quantity > 1 {
+        return format!("{} {}", reward.quantity, item(&reward.item));
+    }
+
+    item(&reward.item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tr_falls_back_to_the_english_text() {
+        // The language is global, so everything that changes it is in this one test.
+        set_language(Language::German);
+        assert_eq!(tr("Settings"), "Einstellungen");
+        assert_eq!(tr("Not in any catalog"), "Not in any catalog");
+        assert_eq!(mission("Not a mission"), "Not a mission");
+        assert_eq!(tier("Unknown"), "Unbekannt");
+        assert_eq!(tier("Lith"), "Lith");
+
+        set_language(Language::English);
+        assert_eq!(tr("Settings"), "Settings");
+    }
+
+    #[test]
+    fn catalogs_have_the_same_strings() {
+        let german = Catalog::load(Language::German).ui;
+        let portuguese = Catalog::load(Language::Portuguese).ui;
+
+        assert!(!german.is_empty());
+        let mut missing = german
+            .keys()
+            .filter(|k| !portuguese.contains_key(*k))
+            .chain(portuguese.keys().filter(|k| !german.contains_key(*k)))
+            .collect::<Vec<&String>>();
+        missing.sort();
+        assert!(missing.is_empty(), "{:?}", missing);
+    }
+}
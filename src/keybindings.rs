@@ -0,0 +1,273 @@
+use eframe::egui::{Event, InputState, Key, Modifiers};
+use std::fmt::{self, Display, Formatter};
+
+/// Something the user can do with a key or from the command palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum Action {
+    ShowFissures,
+    ShowVoidStorms,
+    ShowInvasions,
+    ToggleFilters,
+    Search,
+    OpenNotifications,
+    OpenHistory,
+    RefreshNow,
+    ToggleOverlay,
+    CommandPalette,
+    ShowShortcuts,
+    OpenSettings,
+}
+
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::ShowFissures,
+        Action::ShowVoidStorms,
+        Action::ShowInvasions,
+        Action::ToggleFilters,
+        Action::Search,
+        Action::OpenNotifications,
+        Action::OpenHistory,
+        Action::RefreshNow,
+        Action::ToggleOverlay,
+        Action::CommandPalette,
+        Action::ShowShortcuts,
+        Action::OpenSettings,
+    ];
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Action::ShowFissures => "Show fissures",
+            Action::ShowVoidStorms => "Show void storms",
+            Action::ShowInvasions => "Show invasions",
+            Action::ToggleFilters => "Toggle filters",
+            Action::Search => "Search",
+            Action::OpenNotifications => "Open notifications",
+            Action::OpenHistory => "Open notification history",
+            Action::RefreshNow => "Refresh now",
+            Action::ToggleOverlay => "Overlay",
+            Action::CommandPalette => "Command palette",
+            Action::ShowShortcuts => "Keyboard shortcuts",
+            Action::OpenSettings => "Settings",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Keys that can be bound with their names, egui has no names for the keys.
+const KEYS: [(Key, &str); 52] = [
+    (Key::A, "A"),
+    (Key::B, "B"),
+    (Key::C, "C"),
+    (Key::D, "D"),
+    (Key::E, "E"),
+    (Key::F, "F"),
+    (Key::G, "G"),
+    (Key::H, "H"),
+    (Key::I, "I"),
+    (Key::J, "J"),
+    (Key::K, "K"),
+    (Key::L, "L"),
+    (Key::M, "M"),
+    (Key::N, "N"),
+    (Key::O, "O"),
+    (Key::P, "P"),
+    (Key::Q, "Q"),
+    (Key::R, "R"),
+    (Key::S, "S"),
+    (Key::T, "T"),
+    (Key::U, "U"),
+    (Key::V, "V"),
+    (Key::W, "W"),
+    (Key::X, "X"),
+    (Key::Y, "Y"),
+    (Key::Z, "Z"),
+    (Key::Num0, "0"),
+    (Key::Num1, "1"),
+    (Key::Num2, "2"),
+    (Key::Num3, "3"),
+    (Key::Num4, "4"),
+    (Key::Num5, "5"),
+    (Key::Num6, "6"),
+    (Key::Num7, "7"),
+    (Key::Num8, "8"),
+    (Key::Num9, "9"),
+    (Key::F1, "F1"),
+    (Key::F2, "F2"),
+    (Key::F3, "F3"),
+    (Key::F4, "F4"),
+    (Key::F5, "F5"),
+    (Key::F6, "F6"),
+    (Key::F7, "F7"),
+    (Key::F8, "F8"),
+    (Key::F9, "F9"),
+    (Key::F10, "F10"),
+    (Key::F11, "F11"),
+    (Key::F12, "F12"),
+    (Key::Space, "Space"),
+    (Key::Tab, "Tab"),
+    (Key::Insert, "Insert"),
+    (Key::Delete, "Delete"),
+];
+
+fn key_name(key: Key) -> Option<&'static str> {
+    KEYS.iter().find(|(k, _)| *k == key).map(|(_, name)| *name)
+}
+
+fn key_from_name(name: &str) -> Option<Key> {
+    KEYS.iter().find(|(_, n)| *n == name).map(|(key, _)| *key)
+}
+
+/// Key with the modifiers, e.g: `Ctrl+K`.
+#[derive(Debug, Clone, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct KeyBinding {
+    /// Name of the key in `KEYS`.
+    pub key: String,
+    /// Ctrl, or Cmd on a Mac.
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyBinding {
+    fn new(key: &str, ctrl: bool, shift: bool) -> Self {
+        KeyBinding {
+            key: key.to_string(),
+            ctrl,
+            shift,
+            alt: false,
+        }
+    }
+
+    /// Returns the binding of a key press, `None` for a key that cannot be bound.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+            } => Some(KeyBinding {
+                key: key_name(*key)?.to_string(),
+                ctrl: modifiers.command,
+                shift: modifiers.shift,
+                alt: modifiers.alt,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the key was pressed with exactly these modifiers.
+    fn pressed(&self, input: &InputState) -> bool {
+        let key = match key_from_name(&self.key) {
+            Some(key) => key,
+            None => return false,
+        };
+        let Modifiers {
+            alt,
+            shift,
+            command,
+            ..
+        } = input.modifiers;
+
+        input.key_pressed(key) && self.ctrl == command && self.shift == shift && self.alt == alt
+    }
+
+    /// Function keys work while typing, other keys need a modifier.
+    fn works_while_typing(&self) -> bool {
+        self.ctrl || self.alt || self.key.starts_with('F') && self.key.len() > 1
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl+")?;
+        }
+        if self.shift {
+            f.write_str("Shift+")?;
+        }
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+        f.write_str(&self.key)
+    }
+}
+
+/// Key of every action, `None` when the action has no key.
+#[derive(Debug, Clone, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct Keybindings {
+    pub bindings: Vec<(Action, Option<KeyBinding>)>,
+}
+
+impl Keybindings {
+    /// Returns the key of the `action`.
+    pub fn get(&self, action: Action) -> Option<&KeyBinding> {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .and_then(|(_, binding)| binding.as_ref())
+    }
+
+    /// Bind the `action` to the `binding`, any other action with the same key loses it.
+    pub fn set(&mut self, action: Action, binding: Option<KeyBinding>) {
+        for (a, b) in self.bindings.iter_mut() {
+            if binding.is_some() && *b == binding {
+                *b = None;
+            }
+            if *a == action {
+                *b = binding.clone();
+            }
+        }
+        if !self.bindings.iter().any(|(a, _)| *a == action) {
+            self.bindings.push((action, binding));
+        }
+    }
+
+    /// Returns the actions whose keys were pressed.
+    /// Keys without a modifier are ignored while typing, so they do not trigger from a text field.
+    pub fn pressed(&self, input: &InputState, typing: bool) -> Vec<Action> {
+        self.bindings
+            .iter()
+            .filter_map(|(action, binding)| {
+                let binding = binding.as_ref()?;
+                if typing && !binding.works_while_typing() {
+                    return None;
+                }
+
+                binding.pressed(input).then_some(*action)
+            })
+            .collect()
+    }
+
+    /// Returns the key of the `action` in parenthesis for a menu, empty if it has none.
+    pub fn hint(&self, action: Action) -> String {
+        self.get(action)
+            .map_or_else(String::new, |binding| format!(" ({})", binding))
+    }
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        let defaults = [
+            (Action::ShowFissures, KeyBinding::new("1", true, false)),
+            (Action::ShowVoidStorms, KeyBinding::new("2", true, false)),
+            (Action::ShowInvasions, KeyBinding::new("3", true, false)),
+            (Action::ToggleFilters, KeyBinding::new("F", true, true)),
+            (Action::Search, KeyBinding::new("F", true, false)),
+            (Action::OpenNotifications, KeyBinding::new("N", true, false)),
+            (Action::OpenHistory, KeyBinding::new("H", true, false)),
+            (Action::RefreshNow, KeyBinding::new("F5", false, false)),
+            (Action::ToggleOverlay, KeyBinding::new("F9", false, false)),
+            (Action::CommandPalette, KeyBinding::new("K", true, false)),
+            (Action::ShowShortcuts, KeyBinding::new("F1", false, false)),
+        ];
+
+        Keybindings {
+            bindings: defaults
+                .into_iter()
+                .map(|(action, binding)| (action, Some(binding)))
+                .collect(),
+        }
+    }
+}
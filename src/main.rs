@@ -2,11 +2,32 @@
 
 use crate::ui::UI;
 use crate::util::Resources;
-use crate::voidrat::VoidRat;
+use crate::voidrat::{Options, Storage, STORAGE_FILE};
 use eframe::egui::Vec2;
 use eframe::{IconData, NativeOptions};
 
+pub mod archive;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod clock;
+pub mod countdown;
+pub mod data_pack;
+pub mod diff;
+pub mod drop_table;
+pub mod export;
+pub mod filters;
+pub mod i18n;
+pub mod keybindings;
+pub mod notifications;
+pub mod overlay;
 mod parsers;
+pub mod relics;
+pub mod replay;
+pub mod search;
+pub mod sorting;
+pub mod stats;
+pub mod theme;
+pub mod tray;
 pub mod ui;
 mod util;
 pub mod voidrat;
@@ -15,7 +36,20 @@ pub mod widgets;
 fn main() {
     setup_logging().expect("failed to initialize logging.");
 
+    let app_options = Options::from_args(std::env::args().skip(1));
+    #[cfg(feature = "bench")]
+    if let Some(path) = &app_options.bench {
+        if let Err(e) = bench::run(path) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let icon = Resources::get("icons/voidrat.ico").unwrap().data;
+    let storage = Storage::from_file(STORAGE_FILE);
+    voidrat::set_data_dir(&storage.data_dir);
+    let overlay = storage.overlay;
     let options = NativeOptions {
         initial_window_size: Some(Vec2::new(510.0, 540.0)),
         min_window_size: Some(Vec2::new(510.0, 160.0)),
@@ -30,8 +64,13 @@ fn main() {
         }),
         ..NativeOptions::default()
     };
+    let options = overlay.native_options(options, app_options.click_through);
 
-    eframe::run_native("Voidrat", options, Box::new(|cc| Box::new(UI::new(cc))));
+    eframe::run_native(
+        "Voidrat",
+        options,
+        Box::new(|cc| Box::new(UI::new(cc, app_options, storage))),
+    );
 }
 
 pub fn setup_logging() -> Result<(), fern::InitError> {
@@ -0,0 +1,537 @@
+use crate::clock;
+use crate::parsers::{Fissure, Invasion};
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike, Utc};
+use std::fmt::{self, Display, Formatter};
+
+/// How long (in seconds) history entries are kept after the notified thing has expired.
+const HISTORY_KEEP: i64 = 24 * 60 * 60;
+/// Invasions do not have an expiry time so assume they are gone after this many seconds.
+const INVASION_LIFETIME: i64 = 7 * 24 * 60 * 60;
+
+/// Rules that can make a notification fire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum NotificationRule {
+    /// Fissure with Capture mission in the Void.
+    FissureVoidCapture,
+    /// Invasion with a forma, orokin reactor or orokin catalyst reward.
+    InvasionEpic,
+    /// Reminder before the Cetus night starts.
+    CetusNight,
+    /// Reminder before the Cetus day starts.
+    CetusDay,
+    /// Reminder before a watched fissure expires.
+    FissureExpiry,
+    /// Fissure of a tier with owned relics and a liked mission type.
+    FissureOwnedRelic,
+}
+
+impl Display for NotificationRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NotificationRule::FissureVoidCapture => "Fissure Void Capture",
+            NotificationRule::InvasionEpic => "Invasion epic reward",
+            NotificationRule::CetusNight => "Cetus night soon",
+            NotificationRule::CetusDay => "Cetus day soon",
+            NotificationRule::FissureExpiry => "Watched fissure expiring",
+            NotificationRule::FissureOwnedRelic => "Fissure for owned relics",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How a notification is delivered to the user, from the loudest to the quietest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, bincode::Encode, bincode::Decode)]
+pub enum Delivery {
+    /// Play the notification sound and show it in the UI.
+    Audio,
+    /// Only show it in the UI.
+    Visual,
+    /// Only keep it in the history.
+    Suppressed,
+}
+
+impl Display for Delivery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Delivery::Audio => "Audio",
+            Delivery::Visual => "Visual only",
+            Delivery::Suppressed => "Suppressed",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Weekly time range, in local time, during which notifications are toned down.
+#[derive(Debug, Clone, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct QuietHours {
+    /// Weekdays the range starts on, Monday first.
+    pub days: [bool; 7],
+    /// Start time in minutes after midnight.
+    pub start: u32,
+    /// End time in minutes after midnight.
+    /// When it is before `start` the range continues to the next day.
+    pub end: u32,
+    /// Delivery used during the range.
+    pub delivery: Delivery,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        QuietHours {
+            days: [true; 7],
+            start: 23 * 60,
+            end: 7 * 60,
+            delivery: Delivery::Visual,
+        }
+    }
+}
+
+impl QuietHours {
+    /// Returns true if `time` is within the range.
+    pub fn contains(&self, time: DateTime<Local>) -> bool {
+        let day = time.weekday().num_days_from_monday() as usize;
+        let yesterday = (day + 6) % 7;
+        let minute = time.hour() * 60 + time.minute();
+
+        if self.start <= self.end {
+            self.days[day] && minute >= self.start && minute < self.end
+        } else {
+            (self.days[day] && minute >= self.start) || (self.days[yesterday] && minute < self.end)
+        }
+    }
+}
+
+/// Reminder that fires some time before something happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct Reminder {
+    pub enabled: bool,
+    /// How many minutes before the reminder fires.
+    pub lead_minutes: u32,
+}
+
+impl Default for Reminder {
+    fn default() -> Self {
+        Reminder {
+            enabled: false,
+            lead_minutes: 5,
+        }
+    }
+}
+
+impl Reminder {
+    /// Returns true if the reminder should fire for something that happens `at`.
+    pub fn is_due(&self, at: DateTime<Utc>) -> bool {
+        let now = clock::now();
+
+        self.enabled && now < at && now >= at - Duration::minutes(self.lead_minutes as i64)
+    }
+}
+
+/// Lead times for each reminder type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct Reminders {
+    pub cetus_night: Reminder,
+    pub cetus_day: Reminder,
+    pub fissure_expiry: Reminder,
+}
+
+/// Notification options the user can change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct NotificationSettings {
+    /// Notify when a fissure with Capture mission in the Void shows up.
+    pub fissure_void_capture: bool,
+    /// Notify when an invasion with a forma, orokin reactor or orokin catalyst shows up.
+    pub invasion_epic: bool,
+    /// Notify when a fissure for owned relics with a liked mission type shows up.
+    pub fissure_owned_relic: bool,
+    /// Mission types for the `fissure_owned_relic` rule, e.g: `Capture`.
+    pub liked_missions: Vec<String>,
+    /// Time ranges during which notifications are suppressed or visual only.
+    pub quiet_hours: Vec<QuietHours>,
+    /// Lead times for the reminders.
+    pub reminders: Reminders,
+}
+
+/// Identifies the fissure, invasion or cycle that a notification was about.
+#[derive(Debug, Clone, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct NotificationKey {
+    /// Solar node name, e.g: `Hepit (Void)`.
+    pub node: String,
+    /// Fissure tier, `None` for invasions.
+    pub tier: Option<String>,
+    /// Activation time in seconds.
+    pub activation: i64,
+}
+
+impl Display for NotificationKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.tier {
+            Some(tier) => write!(f, "{} {}", tier, self.node),
+            None => f.write_str(&self.node),
+        }
+    }
+}
+
+impl Fissure {
+    /// Returns the key used to identify this fissure in the notification history.
+    pub fn notification_key(&self) -> NotificationKey {
+        NotificationKey {
+            node: self.node.value.clone(),
+            tier: Some(self.tier.to_string()),
+            activation: self.activation.timestamp(),
+        }
+    }
+}
+
+impl Invasion {
+    /// Returns the key used to identify this invasion in the notification history.
+    pub fn notification_key(&self) -> NotificationKey {
+        NotificationKey {
+            node: self.node.value.clone(),
+            tier: None,
+            activation: self.activation.timestamp(),
+        }
+    }
+}
+
+/// Single entry in the notification history.
+#[derive(Debug, Clone, bincode::Encode, bincode::Decode)]
+pub struct Notification {
+    /// What the notification was about.
+    pub key: NotificationKey,
+    /// Rule that fired.
+    pub rule: NotificationRule,
+    /// When the notification fired in seconds.
+    pub fired_at: i64,
+    /// When the notified thing expires in seconds, if known.
+    pub expires_at: Option<i64>,
+    /// True when the user has dismissed the notification, it cannot be snoozed anymore.
+    pub dismissed: bool,
+    /// Fire the notification again at this time in seconds.
+    pub snoozed_until: Option<i64>,
+    /// How the notification was delivered when it fired.
+    pub delivery: Delivery,
+}
+
+impl Notification {
+    pub fn new(key: NotificationKey, rule: NotificationRule, expires_at: Option<i64>) -> Self {
+        Notification {
+            key,
+            rule,
+            fired_at: clock::now().timestamp(),
+            expires_at,
+            dismissed: false,
+            snoozed_until: None,
+            delivery: Delivery::Audio,
+        }
+    }
+
+    /// Returns the time in seconds after which the entry can be removed from the history.
+    fn prune_at(&self) -> i64 {
+        self.expires_at
+            .unwrap_or(self.key.activation + INVASION_LIFETIME)
+            + HISTORY_KEEP
+    }
+
+    /// Returns true if the notified thing is still around.
+    pub fn is_active(&self) -> bool {
+        self.expires_at
+            .unwrap_or(self.key.activation + INVASION_LIFETIME)
+            > clock::now().timestamp()
+    }
+}
+
+/// Persistent list of fired notifications and muted rules.
+#[derive(Debug, Clone, Default, bincode::Encode, bincode::Decode)]
+pub struct NotificationHistory {
+    /// Fired notifications, oldest first.
+    pub entries: Vec<Notification>,
+    /// Muted rules and the time in seconds when the mute ends.
+    pub muted: Vec<(NotificationRule, i64)>,
+}
+
+impl NotificationHistory {
+    /// Returns true if a notification for `key` has already fired for the `rule`.
+    pub fn contains(&self, rule: NotificationRule, key: &NotificationKey) -> bool {
+        self.entries.iter().any(|n| n.rule == rule && n.key == *key)
+    }
+
+    /// Returns true if the `rule` is currently muted.
+    pub fn is_muted(&self, rule: NotificationRule) -> bool {
+        let now = clock::now().timestamp();
+        self.muted
+            .iter()
+            .any(|(r, until)| *r == rule && *until > now)
+    }
+
+    /// Add a new entry to the history.
+    pub fn push(&mut self, notification: Notification) {
+        self.entries.push(notification);
+    }
+
+    fn find_mut(
+        &mut self,
+        rule: NotificationRule,
+        key: &NotificationKey,
+    ) -> Option<&mut Notification> {
+        self.entries
+            .iter_mut()
+            .find(|n| n.rule == rule && n.key == *key)
+    }
+
+    /// Dismiss the entry, it will not fire again.
+    pub fn dismiss(&mut self, rule: NotificationRule, key: &NotificationKey) {
+        if let Some(n) = self.find_mut(rule, key) {
+            n.dismissed = true;
+            n.snoozed_until = None;
+        }
+    }
+
+    /// Fire the entry again after `minutes`, dismissed entries stay dismissed.
+    pub fn snooze(&mut self, rule: NotificationRule, key: &NotificationKey, minutes: i64) {
+        if let Some(n) = self.find_mut(rule, key).filter(|n| !n.dismissed) {
+            n.snoozed_until = Some(clock::now().timestamp() + minutes * 60);
+        }
+    }
+
+    /// Mute the `rule` until the next daily reset (00:00 UTC).
+    pub fn mute(&mut self, rule: NotificationRule) {
+        let tomorrow = clock::now().naive_utc().date() + Duration::days(1);
+        let reset = Utc
+            .from_utc_datetime(&tomorrow.and_hms_opt(0, 0, 0).unwrap())
+            .timestamp();
+
+        self.unmute(rule);
+        self.muted.push((rule, reset));
+    }
+
+    /// Remove the mute from the `rule`.
+    pub fn unmute(&mut self, rule: NotificationRule) {
+        self.muted.retain(|(r, _)| *r != rule);
+    }
+
+    /// Returns the entries which snooze time has passed and clears their snooze.
+    /// Dismissed entries and entries for things that have already expired are skipped.
+    pub fn take_due_snoozes(&mut self) -> Vec<Notification> {
+        let now = clock::now().timestamp();
+        let mut due = vec![];
+
+        for n in self.entries.iter_mut() {
+            if let Some(until) = n.snoozed_until {
+                if until <= now {
+                    n.snoozed_until = None;
+
+                    if !n.dismissed
+                        && n.is_active()
+                        && !self.muted.iter().any(|(r, u)| *r == n.rule && *u > now)
+                    {
+                        due.push(n.clone());
+                    }
+                }
+            }
+        }
+
+        due
+    }
+
+    /// Remove expired entries and mutes.
+    /// Returns true if anything was removed.
+    pub fn prune(&mut self) -> bool {
+        let now = clock::now().timestamp();
+        let len = self.entries.len() + self.muted.len();
+
+        self.entries.retain(|n| n.prune_at() > now);
+        self.muted.retain(|(_, until)| *until > now);
+
+        len != self.entries.len() + self.muted.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(node: &str) -> NotificationKey {
+        NotificationKey {
+            node: node.to_string(),
+            tier: Some("Lith".to_string()),
+            activation: Utc::now().timestamp() - 600,
+        }
+    }
+
+    /// History with one entry for `node` that expires `expires_in` seconds from now.
+    fn history(node: &str, expires_in: i64) -> NotificationHistory {
+        let mut history = NotificationHistory::default();
+        history.push(Notification::new(
+            key(node),
+            NotificationRule::FissureVoidCapture,
+            Some(Utc::now().timestamp() + expires_in),
+        ));
+
+        history
+    }
+
+    /// Local time on Monday 2022-10-03 (weekday 0) plus `days` at `hour:minute`.
+    fn local(days: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.ymd(2022, 10, 3 + days).and_hms(hour, minute, 0)
+    }
+
+    #[test]
+    fn quiet_hours_within_a_day() {
+        let quiet = QuietHours {
+            days: [true, false, false, false, false, false, false],
+            start: 9 * 60,
+            end: 17 * 60,
+            delivery: Delivery::Visual,
+        };
+
+        assert!(!quiet.contains(local(0, 8, 59)));
+        assert!(quiet.contains(local(0, 9, 0)));
+        assert!(quiet.contains(local(0, 16, 59)));
+        assert!(!quiet.contains(local(0, 17, 0)));
+        assert!(!quiet.contains(local(1, 12, 0)));
+    }
+
+    #[test]
+    fn quiet_hours_wrap_overnight() {
+        let quiet = QuietHours {
+            days: [false, false, false, false, true, false, false],
+            ..Default::default()
+        };
+
+        // Starts on Friday evening and ends on Saturday morning.
+        assert!(!quiet.contains(local(4, 22, 59)));
+        assert!(quiet.contains(local(4, 23, 0)));
+        assert!(quiet.contains(local(5, 0, 30)));
+        assert!(quiet.contains(local(5, 6, 59)));
+        assert!(!quiet.contains(local(5, 7, 0)));
+        assert!(!quiet.contains(local(5, 23, 30)));
+        // Thursday night is not enabled.
+        assert!(!quiet.contains(local(4, 6, 0)));
+    }
+
+    #[test]
+    fn quiet_hours_with_the_same_start_and_end_are_empty() {
+        let quiet = QuietHours {
+            start: 12 * 60,
+            end: 12 * 60,
+            ..Default::default()
+        };
+
+        assert!(!quiet.contains(local(0, 11, 59)));
+        assert!(!quiet.contains(local(0, 12, 0)));
+        assert!(!quiet.contains(local(0, 12, 1)));
+    }
+
+    #[test]
+    fn prune_keeps_entries_for_a_day_after_they_expire() {
+        let now = Utc::now().timestamp();
+        let mut history = history("Hepit (Void)", -HISTORY_KEEP + 600);
+        history.push(Notification::new(
+            key("Ukko (Void)"),
+            NotificationRule::FissureVoidCapture,
+            Some(now - HISTORY_KEEP - 600),
+        ));
+        history.muted = vec![
+            (NotificationRule::CetusDay, now - 60),
+            (NotificationRule::CetusNight, now + 600),
+        ];
+
+        assert!(history.prune());
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].key.node, "Hepit (Void)");
+        assert_eq!(
+            history.muted,
+            vec![(NotificationRule::CetusNight, now + 600)]
+        );
+        assert!(!history.prune());
+    }
+
+    #[test]
+    fn invasions_are_pruned_after_the_assumed_lifetime() {
+        let mut key = key("Kiliken (Venus)");
+        key.tier = None;
+        key.activation = Utc::now().timestamp() - INVASION_LIFETIME - HISTORY_KEEP - 60;
+        let mut history = NotificationHistory::default();
+        history.push(Notification::new(key, NotificationRule::InvasionEpic, None));
+
+        assert!(history.prune());
+        assert!(history.entries.is_empty());
+    }
+
+    #[test]
+    fn due_snoozes_fire_once() {
+        let rule = NotificationRule::FissureVoidCapture;
+        let mut history = history("Hepit (Void)", 3600);
+        history.snooze(rule, &key("Hepit (Void)"), 10);
+
+        assert!(history.take_due_snoozes().is_empty());
+
+        history.entries[0].snoozed_until = Some(Utc::now().timestamp() - 1);
+        let due = history.take_due_snoozes();
+
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].key.node, "Hepit (Void)");
+        assert_eq!(history.entries[0].snoozed_until, None);
+        assert!(history.take_due_snoozes().is_empty());
+    }
+
+    #[test]
+    fn due_snoozes_skip_expired_and_muted_entries() {
+        let rule = NotificationRule::FissureVoidCapture;
+        let past = Some(Utc::now().timestamp() - 1);
+
+        let mut expired = history("Hepit (Void)", -60);
+        expired.entries[0].snoozed_until = past;
+        assert!(expired.take_due_snoozes().is_empty());
+        assert_eq!(expired.entries[0].snoozed_until, None);
+
+        let mut muted = history("Hepit (Void)", 3600);
+        muted.entries[0].snoozed_until = past;
+        muted.mute(rule);
+        assert!(muted.take_due_snoozes().is_empty());
+    }
+
+    #[test]
+    fn dismissed_entries_do_not_come_back() {
+        let rule = NotificationRule::FissureVoidCapture;
+        let key = key("Hepit (Void)");
+        let mut history = history("Hepit (Void)", 3600);
+
+        history.snooze(rule, &key, 10);
+        history.dismiss(rule, &key);
+        assert!(history.entries[0].dismissed);
+        assert_eq!(history.entries[0].snoozed_until, None);
+
+        history.snooze(rule, &key, 10);
+        assert_eq!(history.entries[0].snoozed_until, None);
+
+        history.entries[0].snoozed_until = Some(Utc::now().timestamp() - 1);
+        assert!(history.take_due_snoozes().is_empty());
+    }
+
+    #[test]
+    fn mute_lasts_until_midnight_utc() {
+        let rule = NotificationRule::CetusNight;
+        let mut history = NotificationHistory::default();
+        let before = Utc::now();
+        history.mute(rule);
+        let after = Utc::now();
+
+        let (_, until) = history.muted[0];
+        let until = Utc.timestamp_opt(until, 0).unwrap();
+        assert_eq!((until.hour(), until.minute(), until.second()), (0, 0, 0));
+        assert!(until > after);
+        assert!(until - before <= Duration::days(1));
+        assert!(history.is_muted(rule));
+        assert!(!history.is_muted(NotificationRule::CetusDay));
+
+        history.muted[0].1 = Utc::now().timestamp();
+        assert!(!history.is_muted(rule));
+
+        history.mute(rule);
+        assert_eq!(history.muted.len(), 1);
+        history.unmute(rule);
+        assert!(!history.is_muted(rule));
+    }
+}
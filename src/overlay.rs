@@ -0,0 +1,69 @@
+use eframe::egui::Vec2;
+use eframe::NativeOptions;
+use std::env;
+use std::process::Command;
+
+/// Width of the overlay window.
+pub const OVERLAY_WIDTH: f32 = 260.0;
+/// Command line argument that starts the overlay click-through.
+pub const CLICK_THROUGH_ARG: &str = "--click-through";
+
+/// Compact always on top window with only the filtered fissures and the cycles.
+#[derive(Debug, Clone, Copy, PartialEq, bincode::Encode, bincode::Decode)]
+pub struct Overlay {
+    /// Start in the overlay instead of the full window.
+    pub enabled: bool,
+    /// Opacity of the background, from 0.2 to 1.
+    pub opacity: f32,
+}
+
+impl Default for Overlay {
+    fn default() -> Self {
+        Overlay {
+            enabled: false,
+            opacity: 0.8,
+        }
+    }
+}
+
+impl Overlay {
+    /// Small, transparent, undecorated and always on top window when the overlay is enabled,
+    /// that the mouse clicks pass through when `click_through` is true.
+    pub fn native_options(&self, options: NativeOptions, click_through: bool) -> NativeOptions {
+        if !self.enabled {
+            return options;
+        }
+
+        NativeOptions {
+            always_on_top: true,
+            decorated: false,
+            transparent: true,
+            mouse_passthrough: click_through,
+            initial_window_size: Some(Vec2::new(OVERLAY_WIDTH, 120.0)),
+            min_window_size: Some(Vec2::new(OVERLAY_WIDTH, 40.0)),
+            max_window_size: Some(Vec2::new(OVERLAY_WIDTH, 2000.0)),
+            ..options
+        }
+    }
+
+    /// Start the app again with the same arguments, click-through only if `click_through` is true.
+    /// The window options cannot change after the window is created.
+    /// Click-through is not saved, a click-through window cannot be focused to leave it.
+    pub fn relaunch(click_through: bool) -> Result<(), String> {
+        let exe = env::current_exe().map_err(|e| e.to_string())?;
+        let mut args = env::args()
+            .skip(1)
+            .filter(|a| a != CLICK_THROUGH_ARG)
+            .collect::<Vec<String>>();
+        if click_through {
+            args.push(CLICK_THROUGH_ARG.to_string());
+        }
+
+        Command::new(exe)
+            .args(args)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+
+        Ok(())
+    }
+}
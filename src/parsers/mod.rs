@@ -1,7 +1,11 @@
+use crate::data_pack::DataPack;
 use crate::util::Resources;
 use chrono::{DateTime, TimeZone, Utc};
-use serde::Deserialize;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
 
 pub mod warframestat;
 pub mod world_state;
@@ -38,7 +42,7 @@ pub struct Fissure {
     pub hard: bool,
 }
 
-#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FissureTier {
     Unknown,
     Lith,
@@ -49,6 +53,15 @@ pub enum FissureTier {
 }
 
 impl FissureTier {
+    /// All the real tiers, from the lowest to the highest.
+    pub const ALL: [FissureTier; 5] = [
+        FissureTier::Lith,
+        FissureTier::Meso,
+        FissureTier::Neo,
+        FissureTier::Axi,
+        FissureTier::Requiem,
+    ];
+
     pub fn from_str(string: &str) -> Self {
         match string {
             "Lith" => FissureTier::Lith,
@@ -61,43 +74,113 @@ impl FissureTier {
     }
 }
 
-impl ToString for FissureTier {
-    fn to_string(&self) -> String {
-        match self {
-            FissureTier::Unknown => "Unknown".to_string(),
-            FissureTier::Lith => "Lith".to_string(),
-            FissureTier::Meso => "Meso".to_string(),
-            FissureTier::Neo => "Neo".to_string(),
-            FissureTier::Axi => "Axi".to_string(),
-            FissureTier::Requiem => "Requiem".to_string(),
-        }
+impl Display for FissureTier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FissureTier::Unknown => "Unknown",
+            FissureTier::Lith => "Lith",
+            FissureTier::Meso => "Meso",
+            FissureTier::Neo => "Neo",
+            FissureTier::Axi => "Axi",
+            FissureTier::Requiem => "Requiem",
+        };
+        f.write_str(name)
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SolarNode {
-    /// Human readable name for the solar node.
+    /// Human readable name for the solar node, e.g: `Hepit (Void)`.
     pub value: String,
+    /// Name of the node without the planet, e.g: `Hepit`.
+    #[serde(default)]
+    pub name: String,
+    /// Planet the node is on, e.g: `Void`. `None` for event and PvP nodes.
+    #[serde(default)]
+    pub planet: Option<String>,
     /// Enemy name.
     pub enemy: Option<String>,
     /// Type for the solar node, e.g: Capture.
     #[serde(alias = "type")]
     pub node_type: Option<String>,
+    /// Lowest enemy level.
+    #[serde(default)]
+    pub min_level: Option<u32>,
+    /// Highest enemy level.
+    #[serde(default)]
+    pub max_level: Option<u32>,
+    /// Tileset of the map, e.g: `Orokin Tower`.
+    #[serde(default)]
+    pub tileset: Option<String>,
+    #[serde(default)]
+    pub dark_sector: bool,
+    #[serde(default)]
+    pub archwing: bool,
+    #[serde(default)]
+    pub railjack: bool,
 }
 
 impl Default for SolarNode {
     fn default() -> Self {
         SolarNode {
             value: "Unknown".to_string(),
+            name: "Unknown".to_string(),
+            planet: None,
             enemy: None,
             node_type: None,
+            min_level: None,
+            max_level: None,
+            tileset: None,
+            dark_sector: false,
+            archwing: false,
+            railjack: false,
+        }
+    }
+}
+
+impl SolarNode {
+    /// Returns the enemy level range, e.g: `Lv 10-15`.
+    pub fn level_range(&self) -> Option<String> {
+        match (self.min_level, self.max_level) {
+            (Some(min), Some(max)) => Some(format!("Lv {}-{}", min, max)),
+            (Some(level), None) | (None, Some(level)) => Some(format!("Lv {}", level)),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns true if the node is a Capture mission in the Void.
+    pub fn is_void_capture(&self) -> bool {
+        self.planet.as_deref() == Some("Void") && self.node_type.as_deref() == Some("Capture")
+    }
+
+    /// Fill in the name and the planet from the `value` for data files without them.
+    fn fill_from_value(&mut self) {
+        if !self.name.is_empty() {
+            return;
+        }
+
+        match self.value.rsplit_once(" (") {
+            Some((name, planet)) if planet.ends_with(')') => {
+                self.name = name.to_string();
+                self.planet = Some(planet.trim_end_matches(')').to_string());
+            }
+            _ => self.name = self.value.clone(),
         }
     }
 }
 
-/// All solar nodes in existence.
-#[derive(Debug, Clone, Deserialize)]
-pub struct SolarNodes(HashMap<String, SolarNode>);
+/// All solar nodes in existence, indexed by the key and by the value.
+#[derive(Debug, Clone, Default)]
+pub struct SolarNodes {
+    /// Node key, e.g: `SolNode401`, to the node.
+    by_key: HashMap<String, SolarNode>,
+    /// Node value, e.g: `Hepit (Void)`, to the node key.
+    by_value: HashMap<String, String>,
+}
+
+/// Index shared by all the parsers, `None` until the first lookup.
+static SOLAR_NODES: RwLock<Option<Arc<SolarNodes>>> = RwLock::new(None);
 
 #[derive(Debug, Clone)]
 pub struct InvasionReward {
@@ -139,12 +222,77 @@ pub struct Invasion {
 }
 
 impl SolarNodes {
-    fn find_key_for_value<'a>(
-        map: &'a HashMap<String, SolarNode>,
-        value: &str,
-    ) -> Option<&'a String> {
-        map.iter()
-            .find_map(|(key, val)| if val.value == value { Some(key) } else { None })
+    /// Parse and index the nodes of a solar node data file.
+    pub fn parse(json: &[u8]) -> serde_json::Result<Self> {
+        Ok(Self::index(serde_json::from_slice(json)?))
+    }
+
+    /// Nodes sharing a value are found by the smallest key.
+    fn index(mut by_key: HashMap<String, SolarNode>) -> Self {
+        for node in by_key.values_mut() {
+            node.fill_from_value();
+        }
+
+        let mut keys = by_key.keys().collect::<Vec<&String>>();
+        keys.sort();
+
+        let mut by_value = HashMap::new();
+        for key in keys {
+            by_value
+                .entry(by_key[key].value.clone())
+                .or_insert_with(|| key.clone());
+        }
+
+        SolarNodes { by_key, by_value }
+    }
+
+    /// Returns the nodes with the `nodes` of a data pack added, replacing the ones with the same key.
+    pub fn layered(self, nodes: &HashMap<String, SolarNode>) -> Self {
+        let mut by_key = self.by_key;
+        by_key.extend(nodes.iter().map(|(k, n)| (k.clone(), n.clone())));
+
+        Self::index(by_key)
+    }
+
+    /// Parse the bundled solar node data file.
+    pub fn bundled() -> Self {
+        let sol_data = Resources::get("data/sol_node.json").unwrap().data;
+
+        Self::parse(&sol_data).expect("Bad JSON.")
+    }
+
+    /// Returns the shared index, the bundled data file and the data pack are parsed on the first call.
+    pub fn shared() -> Arc<SolarNodes> {
+        if let Some(nodes) = SOLAR_NODES.read().as_ref() {
+            return nodes.clone();
+        }
+
+        SOLAR_NODES
+            .write()
+            .get_or_insert_with(|| Arc::new(Self::bundled().layered(&DataPack::current().nodes)))
+            .clone()
+    }
+
+    /// Run `swap`, e.g: replace the data pack, and build the shared index again on the next lookup.
+    /// No lookup happens in between, so the index never mixes the old and the new data.
+    pub fn reset_shared(swap: impl FnOnce()) {
+        let mut nodes = SOLAR_NODES.write();
+        swap();
+        *nodes = None;
+    }
+
+    /// Returns the node with the `key`, e.g: `SolNode401`.
+    pub fn get(&self, key: &str) -> Option<&SolarNode> {
+        self.by_key.get(key)
+    }
+
+    /// Returns the node with the `value`, e.g: `Hepit (Void)`.
+    pub fn find(&self, value: &str) -> Option<&SolarNode> {
+        self.get(self.by_value.get(value)?)
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_key.len()
     }
 }
 
@@ -155,27 +303,67 @@ pub trait TennoParser {
     fn parse_fissures(&self, data: &str) -> Vec<Fissure>;
     /// Returns a `CetusCycle`.
     fn parse_cetus_cycle(&self, data: &str) -> CetusCycle;
-    /// Parses solar node data from the local data file.
-    fn solar_nodes(&self) -> SolarNodes {
-        let sol_data = Resources::get("data/sol_node.json").unwrap().data;
-
-        let solar_nodes: SolarNodes = serde_json::from_slice(&sol_data).expect("Bad JSON.");
-        solar_nodes
+    /// Returns the shared solar node index.
+    fn solar_nodes(&self) -> Arc<SolarNodes> {
+        SolarNodes::shared()
     }
     /// Returns the correct solar node based on the key given, e.g: SolNode401.
     fn get_solar_node_by_key(&self, key: &str) -> SolarNode {
-        if let Some(node) = self.solar_nodes().0.get(key) {
-            return node.clone();
-        }
-
-        SolarNode::default()
+        self.solar_nodes().get(key).cloned().unwrap_or_default()
     }
     /// Returns the correct solar node based on the value given, e.g: Hepit (Void).
     fn get_solar_node_by_value(&self, value: &str) -> SolarNode {
-        if let Some(key) = SolarNodes::find_key_for_value(&self.solar_nodes().0, value) {
-            return self.get_solar_node_by_key(key);
+        self.solar_nodes().find(value).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+impl SolarNode {
+    /// Node with the `value`, e.g: `Hepit (Void)`, and the name and the planet taken from it.
+    pub fn test(value: &str) -> Self {
+        let mut node = SolarNode {
+            value: value.to_string(),
+            name: String::new(),
+            ..Default::default()
+        };
+        node.fill_from_value();
+
+        node
+    }
+}
+
+#[cfg(test)]
+impl Fissure {
+    /// Capture fissure on the `node` that has just started and expires in an hour.
+    pub fn test(node: &str, tier: FissureTier) -> Self {
+        let now = Utc::now();
+
+        Fissure {
+            activation: now,
+            expiry: now + chrono::Duration::hours(1),
+            node: SolarNode::test(node),
+            mission: "Capture".to_string(),
+            tier,
+            is_storm: false,
+            hard: false,
         }
+    }
+}
 
-        SolarNode::default()
+#[cfg(test)]
+impl Invasion {
+    /// Invasion on the `node` that has just started, with one `item` as the attacker reward.
+    pub fn test(node: &str, item: &str) -> Self {
+        Invasion {
+            activation: Utc::now(),
+            rewards: InvasionReward {
+                attacker: vec![Reward {
+                    item: item.to_string(),
+                    quantity: 1,
+                }],
+                defender: vec![],
+            },
+            node: SolarNode::test(node),
+        }
     }
 }
@@ -10,6 +10,9 @@ use serde::{Deserialize, Deserializer};
 use serde_json::Value;
 use serde_with::formats::Flexible;
 use serde_with::{serde_as, TimestampMilliSeconds};
+use std::fmt::{self, Display, Formatter};
+
+use crate::data_pack::DataPack;
 
 pub struct WorldState {}
 
@@ -30,19 +33,7 @@ impl TennoParser for WorldState {
                         .attacker_reward
                         .iter()
                         .map(|r| Reward {
-                            item: ITEM_TYPES
-                                .get(&r.item_type)
-                                .unwrap_or(
-                                    &split_pascal_case(
-                                        r.item_type
-                                            .split('/')
-                                            .collect::<Vec<&str>>()
-                                            .last()
-                                            .unwrap(),
-                                    )
-                                    .as_str(),
-                                )
-                                .to_string(),
+                            item: item_name(&r.item_type),
                             quantity: r.item_count,
                         })
                         .collect::<Vec<Reward>>(),
@@ -50,19 +41,7 @@ impl TennoParser for WorldState {
                         .defender_reward
                         .iter()
                         .map(|r| Reward {
-                            item: ITEM_TYPES
-                                .get(&r.item_type)
-                                .unwrap_or(
-                                    &split_pascal_case(
-                                        r.item_type
-                                            .split('/')
-                                            .collect::<Vec<&str>>()
-                                            .last()
-                                            .unwrap(),
-                                    )
-                                    .as_str(),
-                                )
-                                .to_string(),
+                            item: item_name(&r.item_type),
                             quantity: r.item_count,
                         })
                         .collect::<Vec<Reward>>(),
@@ -90,7 +69,7 @@ impl TennoParser for WorldState {
                 activation: f.activation,
                 expiry: f.expiry,
                 node: self.get_solar_node_by_key(&f.node),
-                mission: f.mission_type.clone().unwrap().to_string(),
+                mission: mission_name(f.mission_type.as_deref().unwrap_or_default()),
                 tier: FissureTier::from_str(&f.modifier.to_string()),
                 is_storm: false,
                 hard: f.hard,
@@ -99,17 +78,21 @@ impl TennoParser for WorldState {
 
         let mut storms = _storms
             .iter()
-            .map(|f| Fissure {
-                activation: f.activation,
-                expiry: f.expiry,
-                node: self.get_solar_node_by_key(&f.node),
-                mission: self
-                    .get_solar_node_by_key(&f.node)
-                    .node_type
-                    .unwrap_or_else(|| "Unknown".to_string()),
-                tier: FissureTier::from_str(&f.modifier.to_string()),
-                is_storm: true,
-                hard: false,
+            .map(|f| {
+                let node = self.get_solar_node_by_key(&f.node);
+
+                Fissure {
+                    activation: f.activation,
+                    expiry: f.expiry,
+                    mission: node
+                        .node_type
+                        .clone()
+                        .unwrap_or_else(|| "Unknown".to_string()),
+                    node,
+                    tier: FissureTier::from_str(&f.modifier.to_string()),
+                    is_storm: true,
+                    hard: false,
+                }
             })
             .collect::<Vec<Fissure>>();
 
@@ -146,16 +129,17 @@ pub enum FissureModifier {
     VoidT5,
 }
 
-impl ToString for FissureModifier {
-    fn to_string(&self) -> String {
-        match self {
-            FissureModifier::Unknown => "Unknown".to_string(),
-            FissureModifier::VoidT1 => "Lith".to_string(),
-            FissureModifier::VoidT2 => "Meso".to_string(),
-            FissureModifier::VoidT3 => "Neo".to_string(),
-            FissureModifier::VoidT4 => "Axi".to_string(),
-            FissureModifier::VoidT5 => "Requiem".to_string(),
-        }
+impl Display for FissureModifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FissureModifier::Unknown => "Unknown",
+            FissureModifier::VoidT1 => "Lith",
+            FissureModifier::VoidT2 => "Meso",
+            FissureModifier::VoidT3 => "Neo",
+            FissureModifier::VoidT4 => "Axi",
+            FissureModifier::VoidT5 => "Requiem",
+        };
+        f.write_str(name)
     }
 }
 
@@ -183,51 +167,82 @@ pub enum MissionType {
     MT_SECTOR,
     MT_SURVIVAL,
     MT_TERRITORY,
+    MT_VOID_CASCADE,
+    MT_CORRUPTION,
+    MT_ALCHEMY,
+    /// Mission types this enum does not know about yet.
+    #[serde(other)]
     MT_DEFAULT,
 }
 
-impl ToString for MissionType {
-    fn to_string(&self) -> String {
-        match self {
-            MissionType::MT_ARENA => "Rathuum".to_string(),
-            MissionType::MT_ARTIFACT => "Disruption".to_string(),
-            MissionType::MT_ASSAULT => "Assault".to_string(),
-            MissionType::MT_ASSASSINATION => "Assassination".to_string(),
-            MissionType::MT_CAPTURE => "Capture".to_string(),
-            MissionType::MT_DEFENSE => "Defense".to_string(),
-            MissionType::MT_DISRUPTION => "Disruption".to_string(),
-            MissionType::MT_EVACUATION => "Defection".to_string(),
-            MissionType::MT_EXCAVATE => "Excavation".to_string(),
-            MissionType::MT_EXTERMINATION => "Extermination".to_string(),
-            MissionType::MT_HIVE => "Hive".to_string(),
-            MissionType::MT_INTEL => "Spy".to_string(),
-            MissionType::MT_LANDSCAPE => "Free Roam".to_string(),
-            MissionType::MT_MOBILE_DEFENSE => "Mobile Defense".to_string(),
-            MissionType::MT_PVP => "Conclave".to_string(),
-            MissionType::MT_RESCUE => "Rescue".to_string(),
-            MissionType::MT_RETRIEVAL => "Hijack".to_string(),
-            MissionType::MT_SABOTAGE => "Sabotage".to_string(),
-            MissionType::MT_SECTOR => "Dark Sector".to_string(),
-            MissionType::MT_SURVIVAL => "Survival".to_string(),
-            MissionType::MT_TERRITORY => "Interception".to_string(),
-            MissionType::MT_DEFAULT => "Unknown".to_string(),
-        }
-    }
+impl MissionType {
+    /// All the known mission types.
+    pub const ALL: [MissionType; 24] = [
+        MissionType::MT_ARENA,
+        MissionType::MT_ARTIFACT,
+        MissionType::MT_ASSAULT,
+        MissionType::MT_ASSASSINATION,
+        MissionType::MT_CAPTURE,
+        MissionType::MT_DEFENSE,
+        MissionType::MT_DISRUPTION,
+        MissionType::MT_EVACUATION,
+        MissionType::MT_EXCAVATE,
+        MissionType::MT_EXTERMINATION,
+        MissionType::MT_HIVE,
+        MissionType::MT_INTEL,
+        MissionType::MT_LANDSCAPE,
+        MissionType::MT_MOBILE_DEFENSE,
+        MissionType::MT_PVP,
+        MissionType::MT_RESCUE,
+        MissionType::MT_RETRIEVAL,
+        MissionType::MT_SABOTAGE,
+        MissionType::MT_SECTOR,
+        MissionType::MT_SURVIVAL,
+        MissionType::MT_TERRITORY,
+        MissionType::MT_VOID_CASCADE,
+        MissionType::MT_CORRUPTION,
+        MissionType::MT_ALCHEMY,
+    ];
 }
 
-#[derive(Deserialize, Debug)]
-pub struct SolarNode {
-    pub value: String,
-    pub enemy: Option<String>,
-    #[serde(alias = "type")]
-    pub _type: Option<String>,
+impl Display for MissionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MissionType::MT_ARENA => "Rathuum",
+            MissionType::MT_ARTIFACT => "Disruption",
+            MissionType::MT_ASSAULT => "Assault",
+            MissionType::MT_ASSASSINATION => "Assassination",
+            MissionType::MT_CAPTURE => "Capture",
+            MissionType::MT_DEFENSE => "Defense",
+            MissionType::MT_DISRUPTION => "Disruption",
+            MissionType::MT_EVACUATION => "Defection",
+            MissionType::MT_EXCAVATE => "Excavation",
+            MissionType::MT_EXTERMINATION => "Extermination",
+            MissionType::MT_HIVE => "Hive",
+            MissionType::MT_INTEL => "Spy",
+            MissionType::MT_LANDSCAPE => "Free Roam",
+            MissionType::MT_MOBILE_DEFENSE => "Mobile Defense",
+            MissionType::MT_PVP => "Conclave",
+            MissionType::MT_RESCUE => "Rescue",
+            MissionType::MT_RETRIEVAL => "Hijack",
+            MissionType::MT_SABOTAGE => "Sabotage",
+            MissionType::MT_SECTOR => "Dark Sector",
+            MissionType::MT_SURVIVAL => "Survival",
+            MissionType::MT_TERRITORY => "Interception",
+            MissionType::MT_VOID_CASCADE => "Void Cascade",
+            MissionType::MT_CORRUPTION => "Void Flood",
+            MissionType::MT_ALCHEMY => "Alchemy",
+            MissionType::MT_DEFAULT => "Unknown",
+        };
+        f.write_str(name)
+    }
 }
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct _Fissure {
     pub region: Option<u8>,
-    pub mission_type: Option<MissionType>,
+    pub mission_type: Option<String>,
     pub node: String,
     pub modifier: FissureModifier,
     pub activation: DateTime<Utc>,
@@ -244,7 +259,7 @@ impl<'de> Deserialize<'de> for _Fissure {
         #[serde(rename_all = "PascalCase")]
         struct Outer {
             region: Option<u8>,
-            mission_type: Option<MissionType>,
+            mission_type: Option<String>,
             node: String,
             #[serde(alias = "ActiveMissionTier")]
             modifier: FissureModifier,
@@ -339,6 +354,30 @@ impl<'de> Deserialize<'de> for _SyndicateMission {
     }
 }
 
+/// Returns the name of the item type from the data pack, the bundled names,
+/// or made up from the type, e.g: `/Lotus/Types/Items/Research/ChemComponent` is `Chem Component`.
+fn item_name(item_type: &str) -> String {
+    if let Some(name) = DataPack::current().items.get(item_type) {
+        return name.clone();
+    }
+
+    match ITEM_TYPES.get(item_type) {
+        Some(name) => name.to_string(),
+        None => split_pascal_case(item_type.rsplit('/').next().unwrap_or(item_type)),
+    }
+}
+
+/// Returns the name of the mission type from the data pack or the known ones, e.g: `MT_CAPTURE` is `Capture`.
+fn mission_name(mission_type: &str) -> String {
+    if let Some(name) = DataPack::current().mission_types.get(mission_type) {
+        return name.clone();
+    }
+
+    serde_json::from_value::<MissionType>(Value::String(mission_type.to_string()))
+        .unwrap_or(MissionType::MT_DEFAULT)
+        .to_string()
+}
+
 pub static ITEM_TYPES: phf::Map<&'static str, &'static str> = phf_map! {
     "/Lotus/Types/Items/MiscItems/InfestedAladCoordinate" => "Infested Alad V Nav Coordinate",
     "/Lotus/Types/Items/Research/ChemComponent" => "Detonite Injector",
@@ -0,0 +1,303 @@
+use crate::parsers::FissureTier;
+use crate::voidrat::data_path;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const RELICS_FILE: &str = "relics.json";
+
+/// Relic refinement level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Refinement {
+    #[default]
+    Intact,
+    Exceptional,
+    Flawless,
+    Radiant,
+}
+
+impl Refinement {
+    pub const ALL: [Refinement; 4] = [
+        Refinement::Intact,
+        Refinement::Exceptional,
+        Refinement::Flawless,
+        Refinement::Radiant,
+    ];
+
+    /// Returns the refinement matching the name, case insensitive.
+    pub fn parse(string: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|r| r.to_string().eq_ignore_ascii_case(string.trim()))
+    }
+}
+
+impl Display for Refinement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Refinement::Intact => "Intact",
+            Refinement::Exceptional => "Exceptional",
+            Refinement::Flawless => "Flawless",
+            Refinement::Radiant => "Radiant",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Owned relics of one kind, e.g: 3x Lith A1 Radiant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Relic {
+    pub tier: FissureTier,
+    /// Relic name without the tier, e.g: `A1`.
+    pub name: String,
+    pub count: u32,
+    #[serde(default)]
+    pub refinement: Refinement,
+}
+
+impl Default for Relic {
+    fn default() -> Self {
+        Relic {
+            tier: FissureTier::Lith,
+            name: String::new(),
+            count: 1,
+            refinement: Refinement::Intact,
+        }
+    }
+}
+
+impl Relic {
+    /// Returns true if the `other` relic is the same kind of relic.
+    fn same_kind(&self, other: &Relic) -> bool {
+        self.tier == other.tier
+            && self.refinement == other.refinement
+            && self.name.eq_ignore_ascii_case(&other.name)
+    }
+}
+
+/// All the relics the user owns. Persisted in the data directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelicInventory {
+    pub relics: Vec<Relic>,
+}
+
+impl RelicInventory {
+    fn file_path() -> PathBuf {
+        data_path().join(RELICS_FILE)
+    }
+
+    /// Load the inventory from the data directory.
+    /// Returns an empty inventory if the file is missing or broken.
+    pub fn from_file() -> Self {
+        match fs::read_to_string(Self::file_path()) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!("Cannot parse {}: {}", RELICS_FILE, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Write the inventory to the data directory.
+    pub fn write_to_file(&self) -> std::io::Result<()> {
+        fs::create_dir_all(data_path())?;
+        let json = serde_json::to_string_pretty(self).expect("Cannot serialize relics.");
+
+        fs::write(Self::file_path(), json)
+    }
+
+    /// Returns how many relics of the `tier` are owned.
+    pub fn count_for_tier(&self, tier: &FissureTier) -> u32 {
+        self.relics
+            .iter()
+            .filter(|r| r.tier == *tier)
+            .map(|r| r.count)
+            .sum()
+    }
+
+    /// Add the `relics` to the inventory.
+    /// Counts of relics already in the inventory are replaced by the new ones.
+    pub fn merge(&mut self, relics: Vec<Relic>) {
+        for relic in relics {
+            match self.relics.iter_mut().find(|r| r.same_kind(&relic)) {
+                Some(existing) => existing.count = relic.count,
+                None => self.relics.push(relic),
+            }
+        }
+
+        self.sort();
+    }
+
+    /// Sort by tier and then by name.
+    pub fn sort(&mut self) {
+        self.relics
+            .sort_by(|a, b| a.tier.cmp(&b.tier).then_with(|| a.name.cmp(&b.name)));
+    }
+
+    /// Read relics from a JSON or CSV file.
+    ///
+    /// JSON is either a list of relics or the inventory file itself.
+    /// CSV lines are `tier,name,count,refinement`, refinement being optional.
+    pub fn import(path: &Path) -> Result<Vec<Relic>, String> {
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+        if is_json {
+            serde_json::from_str::<Vec<Relic>>(&content)
+                .or_else(|_| serde_json::from_str::<RelicInventory>(&content).map(|i| i.relics))
+                .map_err(|e| e.to_string())
+        } else {
+            Self::parse_csv(&content)
+        }
+    }
+
+    fn parse_csv(content: &str) -> Result<Vec<Relic>, String> {
+        let mut relics = vec![];
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields = line.split(',').map(|f| f.trim()).collect::<Vec<&str>>();
+            // Skip the header.
+            if i == 0 && fields[0].eq_ignore_ascii_case("tier") {
+                continue;
+            }
+
+            if fields.len() < 3 {
+                return Err(format!("Line {}: expected tier,name,count", i + 1));
+            }
+
+            let tier = FissureTier::from_str(fields[0]);
+            if tier == FissureTier::Unknown {
+                return Err(format!("Line {}: unknown tier {}", i + 1, fields[0]));
+            }
+
+            let count = fields[2]
+                .parse::<u32>()
+                .map_err(|_| format!("Line {}: bad count {}", i + 1, fields[2]))?;
+
+            let refinement = match fields.get(3) {
+                Some(r) if !r.is_empty() => Refinement::parse(r)
+                    .ok_or_else(|| format!("Line {}: unknown refinement {}", i + 1, r))?,
+                _ => Refinement::Intact,
+            };
+
+            relics.push(Relic {
+                tier,
+                name: fields[1].to_string(),
+                count,
+                refinement,
+            });
+        }
+
+        Ok(relics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relic(tier: FissureTier, name: &str, count: u32, refinement: Refinement) -> Relic {
+        Relic {
+            tier,
+            name: name.to_string(),
+            count,
+            refinement,
+        }
+    }
+
+    #[test]
+    fn csv_rows_become_relics() {
+        let csv = "Tier,Name,Count,Refinement\n\
+                   # Farmed this week\n\
+                   Lith, A1, 3\n\
+                   \n\
+                   Axi,V8,1,radiant\n\
+                   Meso,N2,2,\n";
+
+        assert_eq!(
+            RelicInventory::parse_csv(csv),
+            Ok(vec![
+                relic(FissureTier::Lith, "A1", 3, Refinement::Intact),
+                relic(FissureTier::Axi, "V8", 1, Refinement::Radiant),
+                relic(FissureTier::Meso, "N2", 2, Refinement::Intact),
+            ])
+        );
+    }
+
+    #[test]
+    fn malformed_rows_are_reported_with_the_line() {
+        for (csv, error) in [
+            ("Lith,A1", "Line 1: expected tier,name,count"),
+            ("Lith,A1,3\nVoid,A1,3", "Line 2: unknown tier Void"),
+            ("Lith,A1,many", "Line 1: bad count many"),
+            ("Lith,A1,-1", "Line 1: bad count -1"),
+            ("Lith,A1,3,Shiny", "Line 1: unknown refinement Shiny"),
+        ] {
+            assert_eq!(RelicInventory::parse_csv(csv), Err(error.to_string()));
+        }
+    }
+
+    #[test]
+    fn refinements_parse_ignoring_case_and_spaces() {
+        assert_eq!(Refinement::parse(" FLAWLESS "), Some(Refinement::Flawless));
+        assert_eq!(
+            Refinement::parse("exceptional"),
+            Some(Refinement::Exceptional)
+        );
+        assert_eq!(Refinement::parse("Shiny"), None);
+        assert_eq!(Refinement::parse(""), None);
+    }
+
+    #[test]
+    fn merging_replaces_the_counts_of_the_same_kind() {
+        let mut inventory = RelicInventory {
+            relics: vec![
+                relic(FissureTier::Neo, "V8", 1, Refinement::Intact),
+                relic(FissureTier::Lith, "A1", 2, Refinement::Intact),
+            ],
+        };
+
+        inventory.merge(vec![
+            relic(FissureTier::Lith, "a1", 5, Refinement::Intact),
+            relic(FissureTier::Lith, "A1", 1, Refinement::Radiant),
+            relic(FissureTier::Meso, "N2", 4, Refinement::Intact),
+            relic(FissureTier::Meso, "N2", 3, Refinement::Intact),
+        ]);
+
+        assert_eq!(
+            inventory.relics,
+            [
+                relic(FissureTier::Lith, "A1", 5, Refinement::Intact),
+                relic(FissureTier::Lith, "A1", 1, Refinement::Radiant),
+                relic(FissureTier::Meso, "N2", 3, Refinement::Intact),
+                relic(FissureTier::Neo, "V8", 1, Refinement::Intact),
+            ]
+        );
+    }
+
+    #[test]
+    fn tier_counts_add_up_every_refinement() {
+        let inventory = RelicInventory {
+            relics: vec![
+                relic(FissureTier::Lith, "A1", 5, Refinement::Intact),
+                relic(FissureTier::Lith, "A1", 1, Refinement::Radiant),
+                relic(FissureTier::Lith, "B2", 2, Refinement::Flawless),
+                relic(FissureTier::Axi, "V8", 1, Refinement::Intact),
+            ],
+        };
+
+        assert_eq!(inventory.count_for_tier(&FissureTier::Lith), 8);
+        assert_eq!(inventory.count_for_tier(&FissureTier::Axi), 1);
+        assert_eq!(inventory.count_for_tier(&FissureTier::Requiem), 0);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::clock::{Clock, VirtualClock};
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Replay speeds the user can pick from, virtual seconds per real second.
+pub const REPLAY_SPEEDS: [f64; 5] = [1.0, 10.0, 60.0, 300.0, 1800.0];
+
+/// World state snapshot recorded at some time.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub time: DateTime<Utc>,
+    pub path: PathBuf,
+}
+
+/// Plays back world state snapshots from a directory with a virtual clock.
+#[derive(Debug)]
+pub struct Replay {
+    /// Snapshots, oldest first.
+    snapshots: Vec<Snapshot>,
+    clock: Arc<VirtualClock>,
+}
+
+impl Replay {
+    /// Load the snapshots from the `dir`.
+    ///
+    /// The snapshot time is the file name in seconds, e.g: `1664625600.json`,
+    /// or the `Time` field of the world state.
+    /// The clock starts paused at the first snapshot.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let mut snapshots = vec![];
+
+        for entry in fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if !path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+            {
+                continue;
+            }
+
+            match snapshot_time(&path) {
+                Some(time) => snapshots.push(Snapshot { time, path }),
+                None => return Err(format!("{}: no timestamp", path.display())),
+            }
+        }
+
+        if snapshots.is_empty() {
+            return Err(format!("{}: no snapshots", dir.display()));
+        }
+
+        snapshots.sort_by_key(|s| s.time);
+        let clock = Arc::new(VirtualClock::new(snapshots[0].time));
+
+        Ok(Replay { snapshots, clock })
+    }
+
+    pub fn clock(&self) -> &Arc<VirtualClock> {
+        &self.clock
+    }
+
+    pub fn snapshots(&self) -> &[Snapshot] {
+        &self.snapshots
+    }
+
+    /// Returns the index of the latest snapshot at the current virtual time.
+    pub fn current(&self) -> usize {
+        let now = self.clock.now();
+
+        self.snapshots
+            .iter()
+            .rposition(|s| s.time <= now)
+            .unwrap_or(0)
+    }
+
+    /// Pause and move the clock to the next snapshot, or the previous one when `forward` is false.
+    pub fn step(&self, forward: bool) {
+        let current = self.current();
+        let index = if forward {
+            (current + 1).min(self.snapshots.len() - 1)
+        } else {
+            current.saturating_sub(1)
+        };
+
+        self.clock.set_paused(true);
+        self.clock.set(self.snapshots[index].time);
+    }
+
+    /// Returns the world state of the snapshot at `index`.
+    pub fn read(&self, index: usize) -> std::io::Result<String> {
+        fs::read_to_string(&self.snapshots[index].path)
+    }
+}
+
+/// Write the world state `json` to the `dir` as a snapshot for a later replay.
+pub fn record(dir: &Path, json: &str) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    fs::write(
+        dir.join(format!("{}.json", crate::clock::now().timestamp())),
+        json,
+    )
+}
+
+fn snapshot_time(path: &Path) -> Option<DateTime<Utc>> {
+    let seconds = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .and_then(|s| s.parse::<i64>().ok())
+        .or_else(|| {
+            let json = fs::read_to_string(path).ok()?;
+            serde_json::from_str::<Value>(&json).ok()?["Time"].as_i64()
+        })?;
+
+    Utc.timestamp_opt(seconds, 0).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Directory with a recording of three snapshots an hour apart and a file that is not one.
+    fn recording(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("voidrat-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(dir.join("1664629200.json"), r#"{"Time": 1}"#).unwrap();
+        fs::write(dir.join("1664625600.json"), "{}").unwrap();
+        // Named by hand, the time comes from the world state.
+        fs::write(dir.join("evening.json"), r#"{"Time": 1664632800}"#).unwrap();
+        fs::write(dir.join("notes.txt"), "not a snapshot").unwrap();
+
+        dir
+    }
+
+    fn time(seconds: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn load_sorts_the_snapshots_and_starts_paused_at_the_first() {
+        let dir = recording("load");
+        let replay = Replay::load(&dir).unwrap();
+
+        let times = replay
+            .snapshots()
+            .iter()
+            .map(|s| s.time.timestamp())
+            .collect::<Vec<i64>>();
+        assert_eq!(times, [1664625600, 1664629200, 1664632800]);
+        assert!(replay.clock().is_paused());
+        assert_eq!(replay.clock().now(), time(1664625600));
+        assert_eq!(replay.current(), 0);
+        assert_eq!(replay.read(2).unwrap(), r#"{"Time": 1664632800}"#);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn load_fails_without_snapshots_or_timestamps() {
+        let dir = std::env::temp_dir().join(format!("voidrat-empty-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        assert!(Replay::load(&dir).unwrap_err().ends_with("no snapshots"));
+
+        fs::write(dir.join("broken.json"), "{}").unwrap();
+        assert!(Replay::load(&dir).unwrap_err().ends_with("no timestamp"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn steps_stop_at_the_start_and_the_end() {
+        let dir = recording("step");
+        let replay = Replay::load(&dir).unwrap();
+
+        replay.step(false);
+        assert_eq!(replay.current(), 0);
+        assert_eq!(replay.clock().now(), time(1664625600));
+
+        replay.step(true);
+        replay.step(true);
+        assert_eq!(replay.current(), 2);
+        replay.step(true);
+        assert_eq!(replay.current(), 2);
+        assert_eq!(replay.clock().now(), time(1664632800));
+
+        // Between two snapshots the earlier one is current.
+        replay.clock().set(time(1664629200 + 60));
+        assert_eq!(replay.current(), 1);
+        replay.step(false);
+        assert_eq!(replay.current(), 0);
+
+        // Before the first snapshot the first one is current.
+        replay.clock().set(time(0));
+        assert_eq!(replay.current(), 0);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
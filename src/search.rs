@@ -0,0 +1,166 @@
+use crate::parsers::{Fissure, Invasion};
+use std::cmp::Reverse;
+
+/// Returns how well the `query` matches the `text`, `None` if it does not match.
+///
+/// Every character of the query has to be found in the text in the same order, ignoring case.
+/// Consecutive characters and characters at the start of a word score higher.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text = text.to_lowercase().chars().collect::<Vec<char>>();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for q in query.to_lowercase().chars() {
+        let found = position + text[position..].iter().position(|&c| c == q)?;
+
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 2;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+
+        previous = Some(found);
+        position = found + 1;
+    }
+
+    Some(score)
+}
+
+/// Returns the score of the best matching `fields` for every word of the `query`,
+/// `None` if some word does not match any field. An empty query matches everything.
+fn score_fields(query: &str, fields: &[String]) -> Option<u32> {
+    query.split_whitespace().try_fold(0, |score, word| {
+        let best = fields.iter().filter_map(|f| fuzzy_score(word, f)).max()?;
+
+        Some(score + best)
+    })
+}
+
+/// Matches the node name and planet, mission type, enemy faction, tier and tileset.
+pub fn fissure_score(query: &str, fissure: &Fissure) -> Option<u32> {
+    let mut fields = vec![
+        fissure.node.value.clone(),
+        fissure.mission.clone(),
+        fissure.tier.to_string(),
+    ];
+    fields.extend(fissure.node.enemy.clone());
+    fields.extend(fissure.node.tileset.clone());
+
+    score_fields(query, &fields)
+}
+
+/// Matches the node name and planet, enemy faction and reward item names.
+pub fn invasion_score(query: &str, invasion: &Invasion) -> Option<u32> {
+    let mut fields = vec![invasion.node.value.clone()];
+    fields.extend(invasion.node.enemy.clone());
+    fields.extend(
+        invasion
+            .rewards
+            .attacker
+            .iter()
+            .chain(&invasion.rewards.defender)
+            .map(|r| r.item.clone()),
+    );
+
+    score_fields(query, &fields)
+}
+
+/// Fissure, void storm or invasion that matches a search.
+#[derive(Debug, Clone)]
+pub enum SearchHit {
+    Fissure(Fissure),
+    Invasion(Invasion),
+}
+
+/// Search the `fissures` and the `invasions`, best matches first.
+pub fn search(query: &str, fissures: &[&Fissure], invasions: &[Invasion]) -> Vec<(u32, SearchHit)> {
+    if query.trim().is_empty() {
+        return vec![];
+    }
+
+    let mut hits = fissures
+        .iter()
+        .filter_map(|f| Some((fissure_score(query, f)?, SearchHit::Fissure((*f).clone()))))
+        .chain(
+            invasions
+                .iter()
+                .filter_map(|i| Some((invasion_score(query, i)?, SearchHit::Invasion(i.clone())))),
+        )
+        .collect::<Vec<(u32, SearchHit)>>();
+
+    hits.sort_by_key(|(score, _)| Reverse(*score));
+
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::FissureTier;
+
+    fn hepit() -> Fissure {
+        let mut hepit = Fissure::test("Hepit (Void)", FissureTier::Lith);
+        hepit.node.enemy = Some("Corrupted".to_string());
+
+        hepit
+    }
+
+    #[test]
+    fn characters_match_in_order_ignoring_case() {
+        assert!(fuzzy_score("hpt", "Hepit (Void)").is_some());
+        assert!(fuzzy_score("HEPIT", "Hepit (Void)").is_some());
+        assert!(fuzzy_score("tph", "Hepit (Void)").is_none());
+        assert!(fuzzy_score("hepitx", "Hepit (Void)").is_none());
+    }
+
+    #[test]
+    fn consecutive_and_word_start_characters_score_higher() {
+        let consecutive = fuzzy_score("cap", "Capture").unwrap();
+        let scattered = fuzzy_score("cte", "Capture").unwrap();
+        assert!(consecutive > scattered);
+
+        let word_start = fuzzy_score("v", "Hepit (Void)").unwrap();
+        let inside = fuzzy_score("i", "Hepit (Void)").unwrap();
+        assert!(word_start > inside);
+    }
+
+    #[test]
+    fn every_word_has_to_match_some_field() {
+        let hepit = hepit();
+
+        assert!(fissure_score("hepit capture", &hepit).is_some());
+        assert!(fissure_score("lith corrupted", &hepit).is_some());
+        assert!(fissure_score("hepit survival", &hepit).is_none());
+        assert_eq!(fissure_score("", &hepit), Some(0));
+    }
+
+    #[test]
+    fn invasions_match_the_reward_items() {
+        let kiliken = Invasion::test("Kiliken (Venus)", "Forma Blueprint");
+
+        assert!(invasion_score("forma", &kiliken).is_some());
+        assert!(invasion_score("venus", &kiliken).is_some());
+        assert!(invasion_score("catalyst", &kiliken).is_none());
+    }
+
+    #[test]
+    fn best_matches_come_first() {
+        let hepit = hepit();
+        let mut ukko = Fissure::test("Ukko (Void)", FissureTier::Axi);
+        ukko.mission = "Survival".to_string();
+        let invasions = [Invasion::test("Kiliken (Venus)", "Forma Blueprint")];
+
+        let hits = search("void capture", &[&ukko, &hepit], &invasions);
+        assert_eq!(hits.len(), 1);
+        assert!(matches!(&hits[0].1, SearchHit::Fissure(f) if f.node.value == "Hepit (Void)"));
+
+        let hits = search("v", &[&hepit], &invasions);
+        assert_eq!(hits.len(), 2);
+        assert!(hits[0].0 >= hits[1].0);
+
+        assert!(search("  ", &[&hepit], &[]).is_empty());
+    }
+}
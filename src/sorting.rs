@@ -0,0 +1,274 @@
+use crate::parsers::{Fissure, Invasion};
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+/// Column a table can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum SortKey {
+    Tier,
+    /// Time left for fissures, time active for invasions.
+    Time,
+    Mission,
+    Planet,
+    Node,
+}
+
+impl Display for SortKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SortKey::Tier => "Tier",
+            SortKey::Time => "Time",
+            SortKey::Mission => "Mission",
+            SortKey::Planet => "Planet",
+            SortKey::Node => "Node",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How table rows are grouped under collapsible headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum GroupBy {
+    None,
+    Tier,
+    Planet,
+}
+
+impl Display for GroupBy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            GroupBy::None => "No grouping",
+            GroupBy::Tier => "Group by tier",
+            GroupBy::Planet => "Group by planet",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Sorting and grouping of a single table view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct TableSorting {
+    pub key: SortKey,
+    pub descending: bool,
+    pub group: GroupBy,
+}
+
+impl Default for TableSorting {
+    fn default() -> Self {
+        TableSorting {
+            key: SortKey::Tier,
+            descending: false,
+            group: GroupBy::None,
+        }
+    }
+}
+
+impl TableSorting {
+    /// Sort by the `key`, or reverse the order if the table is already sorted by it.
+    pub fn toggle(&mut self, key: SortKey) {
+        if self.key == key {
+            self.descending = !self.descending;
+        } else {
+            self.key = key;
+            self.descending = false;
+        }
+    }
+
+    /// Arrow for the column header of the `key`, empty if the table is not sorted by it.
+    pub fn arrow(&self, key: SortKey) -> &str {
+        match (self.key == key, self.descending) {
+            (false, _) => "",
+            (true, false) => " ⏶",
+            (true, true) => " ⏷",
+        }
+    }
+
+    pub fn sort_fissures(&self, fissures: &mut [Fissure]) {
+        fissures.sort_by(|a, b| {
+            let ordering = match self.key {
+                SortKey::Tier => a.tier.cmp(&b.tier),
+                SortKey::Time => a.expiry.cmp(&b.expiry),
+                SortKey::Mission => a.mission.cmp(&b.mission),
+                SortKey::Planet => a.node.planet.cmp(&b.node.planet),
+                SortKey::Node => a.node.value.cmp(&b.node.value),
+            }
+            .then_with(|| a.tier.cmp(&b.tier))
+            .then_with(|| a.expiry.cmp(&b.expiry));
+
+            // Groups are always in ascending order.
+            let group = match self.group {
+                GroupBy::None => Ordering::Equal,
+                GroupBy::Tier => a.tier.cmp(&b.tier),
+                GroupBy::Planet => a.node.planet.cmp(&b.node.planet),
+            };
+
+            group.then(self.direction(ordering))
+        });
+    }
+
+    /// Invasions do not have a tier or a mission, sorting by them sorts by the node.
+    pub fn sort_invasions(&self, invasions: &mut [Invasion]) {
+        invasions.sort_by(|a, b| {
+            let ordering = match self.key {
+                SortKey::Time => b.activation.cmp(&a.activation),
+                SortKey::Planet => a.node.planet.cmp(&b.node.planet),
+                SortKey::Tier | SortKey::Mission | SortKey::Node => a.node.value.cmp(&b.node.value),
+            }
+            .then_with(|| a.node.value.cmp(&b.node.value));
+
+            let group = match self.group {
+                GroupBy::None | GroupBy::Tier => Ordering::Equal,
+                GroupBy::Planet => a.node.planet.cmp(&b.node.planet),
+            };
+
+            group.then(self.direction(ordering))
+        });
+    }
+
+    fn direction(&self, ordering: Ordering) -> Ordering {
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    /// Returns the name of the group the fissure belongs to, `None` when not grouping.
+    pub fn fissure_group(&self, fissure: &Fissure) -> Option<String> {
+        match self.group {
+            GroupBy::None => None,
+            GroupBy::Tier => Some(fissure.tier.to_string()),
+            GroupBy::Planet => Some(
+                fissure
+                    .node
+                    .planet
+                    .clone()
+                    .unwrap_or_else(|| "Unknown".to_string()),
+            ),
+        }
+    }
+
+    /// Returns the name of the group the invasion belongs to, `None` when not grouping.
+    /// Invasions do not have a tier so they are not grouped by it.
+    pub fn invasion_group(&self, invasion: &Invasion) -> Option<String> {
+        match self.group {
+            GroupBy::None | GroupBy::Tier => None,
+            GroupBy::Planet => Some(
+                invasion
+                    .node
+                    .planet
+                    .clone()
+                    .unwrap_or_else(|| "Unknown".to_string()),
+            ),
+        }
+    }
+}
+
+/// Split the sorted `items` into groups, keeping the order.
+/// Items without a group end up in a single unnamed group.
+pub fn group<T>(
+    items: Vec<T>,
+    group_of: impl Fn(&T) -> Option<String>,
+) -> Vec<(Option<String>, Vec<T>)> {
+    let mut groups: Vec<(Option<String>, Vec<T>)> = vec![];
+
+    for item in items {
+        let name = group_of(&item);
+        match groups.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, items)) => items.push(item),
+            None => groups.push((name, vec![item])),
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::FissureTier;
+    use chrono::{Duration, Utc};
+
+    fn fissure(node: &str, tier: FissureTier, minutes_left: i64) -> Fissure {
+        Fissure {
+            expiry: Utc::now() + Duration::minutes(minutes_left),
+            ..Fissure::test(node, tier)
+        }
+    }
+
+    fn nodes(fissures: &[Fissure]) -> Vec<&str> {
+        fissures.iter().map(|f| f.node.name.as_str()).collect()
+    }
+
+    #[test]
+    fn toggle_reverses_the_same_key_and_resets_on_another() {
+        let mut sorting = TableSorting::default();
+
+        sorting.toggle(SortKey::Tier);
+        assert!(sorting.descending);
+        assert_eq!(sorting.arrow(SortKey::Tier), " ⏷");
+
+        sorting.toggle(SortKey::Time);
+        assert_eq!(sorting.key, SortKey::Time);
+        assert!(!sorting.descending);
+        assert_eq!(sorting.arrow(SortKey::Time), " ⏶");
+        assert_eq!(sorting.arrow(SortKey::Tier), "");
+    }
+
+    #[test]
+    fn fissures_sort_by_the_key_then_the_tier() {
+        let mut fissures = vec![
+            fissure("Ukko (Void)", FissureTier::Axi, 10),
+            fissure("Hepit (Void)", FissureTier::Lith, 30),
+            fissure("Cinxia (Ceres)", FissureTier::Meso, 20),
+        ];
+        let mut sorting = TableSorting::default();
+
+        sorting.sort_fissures(&mut fissures);
+        assert_eq!(nodes(&fissures), ["Hepit", "Cinxia", "Ukko"]);
+
+        sorting.toggle(SortKey::Time);
+        sorting.sort_fissures(&mut fissures);
+        assert_eq!(nodes(&fissures), ["Ukko", "Cinxia", "Hepit"]);
+
+        sorting.toggle(SortKey::Time);
+        sorting.sort_fissures(&mut fissures);
+        assert_eq!(nodes(&fissures), ["Hepit", "Cinxia", "Ukko"]);
+
+        // Same planet, the tier decides.
+        sorting.toggle(SortKey::Planet);
+        sorting.sort_fissures(&mut fissures);
+        assert_eq!(nodes(&fissures), ["Cinxia", "Hepit", "Ukko"]);
+    }
+
+    #[test]
+    fn groups_stay_ascending_when_descending() {
+        let mut fissures = vec![
+            fissure("Hepit (Void)", FissureTier::Lith, 30),
+            fissure("Cinxia (Ceres)", FissureTier::Lith, 20),
+            fissure("Ukko (Void)", FissureTier::Axi, 10),
+        ];
+        let sorting = TableSorting {
+            key: SortKey::Time,
+            descending: true,
+            group: GroupBy::Tier,
+        };
+
+        sorting.sort_fissures(&mut fissures);
+        assert_eq!(nodes(&fissures), ["Hepit", "Cinxia", "Ukko"]);
+
+        let groups = group(fissures, |f| sorting.fissure_group(f));
+        let names = groups
+            .iter()
+            .map(|(name, items)| (name.clone().unwrap(), items.len()))
+            .collect::<Vec<(String, usize)>>();
+        assert_eq!(names, [("Lith".to_string(), 2), ("Axi".to_string(), 1)]);
+    }
+
+    #[test]
+    fn items_without_a_group_end_up_together() {
+        let groups = group(vec![1, 2, 3], |_| None);
+
+        assert_eq!(groups, [(None, vec![1, 2, 3])]);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::archive::{Archive, NodeCount};
+use crate::parsers::{FissureTier, SolarNode, SolarNodes};
+use crate::util::csv_line;
+use crate::voidrat::data_path;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+const STATS_DIR: &str = "stats";
+
+/// Aggregates over the archived fissures and invasions.
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    /// Fissure count of each tier per day, days counted from the UNIX epoch.
+    pub tiers_per_day: BTreeMap<FissureTier, Vec<(i64, u32)>>,
+    /// Fissure count of each mission type per tier, most common first.
+    pub missions: BTreeMap<FissureTier, Vec<(String, u32)>>,
+    /// Average fissure lifetime in minutes per tier.
+    pub lifetimes: BTreeMap<FissureTier, f64>,
+    /// Fissure counts per node, most common first.
+    pub nodes: Vec<NodeCount>,
+    /// How many invasions had each reward, most common first.
+    pub invasion_rewards: Vec<(String, u32)>,
+}
+
+impl NodeCount {
+    /// Returns true if the node is a Capture mission in the Void.
+    pub fn is_void_capture(&self) -> bool {
+        SolarNodes::shared()
+            .find(&self.node)
+            .is_some_and(SolarNode::is_void_capture)
+    }
+}
+
+impl Stats {
+    /// Collect the statistics from the archive.
+    pub fn collect(archive: &Archive) -> rusqlite::Result<Self> {
+        let mut stats = Stats::default();
+
+        for (day, tier, count) in archive.tier_counts_per_day()? {
+            stats
+                .tiers_per_day
+                .entry(tier)
+                .or_default()
+                .push((day, count));
+        }
+
+        for (tier, mission, count) in archive.mission_counts()? {
+            stats
+                .missions
+                .entry(tier)
+                .or_default()
+                .push((mission, count));
+        }
+
+        for (tier, seconds) in archive.average_lifetimes()? {
+            stats.lifetimes.insert(tier, seconds / 60.0);
+        }
+
+        stats.nodes = archive.node_counts()?;
+
+        let mut rewards: BTreeMap<String, u32> = BTreeMap::new();
+        for invasion in archive.invasion_rewards()? {
+            for reward in invasion.split(", ").filter(|r| !r.is_empty()) {
+                *rewards.entry(item_name(reward).to_string()).or_default() += 1;
+            }
+        }
+        stats.invasion_rewards = rewards.into_iter().collect();
+        stats
+            .invasion_rewards
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Ok(stats)
+    }
+
+    /// Write every aggregate to its own CSV file in the data directory.
+    /// Returns the directory the files were written to.
+    pub fn write_csv(&self) -> std::io::Result<PathBuf> {
+        let dir = data_path().join(STATS_DIR);
+        fs::create_dir_all(&dir)?;
+
+        let mut tiers = vec![csv_line(&["day".into(), "tier".into(), "count".into()])];
+        for (tier, days) in &self.tiers_per_day {
+            for (day, count) in days {
+                tiers.push(csv_line(&[
+                    day_to_string(*day),
+                    tier.to_string(),
+                    count.to_string(),
+                ]));
+            }
+        }
+
+        let mut missions = vec![csv_line(&["tier".into(), "mission".into(), "count".into()])];
+        for (tier, counts) in &self.missions {
+            for (mission, count) in counts {
+                missions.push(csv_line(&[
+                    tier.to_string(),
+                    mission.clone(),
+                    count.to_string(),
+                ]));
+            }
+        }
+
+        let mut lifetimes = vec![csv_line(&["tier".into(), "average_minutes".into()])];
+        for (tier, minutes) in &self.lifetimes {
+            lifetimes.push(csv_line(&[tier.to_string(), format!("{:.1}", minutes)]));
+        }
+
+        let mut nodes = vec![csv_line(&[
+            "node".into(),
+            "mission".into(),
+            "fissures".into(),
+            "steel_path".into(),
+            "void_storms".into(),
+            "void_capture".into(),
+        ])];
+        for n in &self.nodes {
+            nodes.push(csv_line(&[
+                n.node.clone(),
+                n.mission.clone(),
+                n.total.to_string(),
+                n.hard.to_string(),
+                n.storm.to_string(),
+                n.is_void_capture().to_string(),
+            ]));
+        }
+
+        let mut rewards = vec![csv_line(&["reward".into(), "invasions".into()])];
+        for (reward, count) in &self.invasion_rewards {
+            rewards.push(csv_line(&[reward.clone(), count.to_string()]));
+        }
+
+        for (name, lines) in [
+            ("tiers_per_day.csv", tiers),
+            ("missions.csv", missions),
+            ("lifetimes.csv", lifetimes),
+            ("nodes.csv", nodes),
+            ("invasion_rewards.csv", rewards),
+        ] {
+            fs::write(dir.join(name), lines.join("\n") + "\n")?;
+        }
+
+        Ok(dir)
+    }
+}
+
+/// Returns the date of the `day` counted from the UNIX epoch, e.g: `2022-10-01`.
+pub fn day_to_string(day: i64) -> String {
+    chrono::NaiveDateTime::from_timestamp_opt(day * 24 * 60 * 60, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Strip the quantity from a reward, e.g: `3 Fieldron` -> `Fieldron`.
+fn item_name(reward: &str) -> &str {
+    match reward.split_once(' ') {
+        Some((quantity, item)) if quantity.parse::<u32>().is_ok() => item,
+        _ => reward,
+    }
+}
@@ -0,0 +1,255 @@
+use eframe::egui::Color32;
+use parking_lot::RwLock;
+use std::fmt::{self, Display, Formatter};
+
+/// Color as red, green and blue.
+pub type Rgb = [u8; 3];
+
+pub fn color(rgb: Rgb) -> Color32 {
+    Color32::from_rgb(rgb[0], rgb[1], rgb[2])
+}
+
+/// Fill and border of a badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct BadgeColors {
+    pub fill: Rgb,
+    pub border: Rgb,
+}
+
+impl BadgeColors {
+    pub fn colors(&self) -> (Color32, Color32) {
+        (color(self.fill), color(self.border))
+    }
+}
+
+/// Colors of the whole UI.
+///
+/// https://yeun.github.io/open-color/ingredients.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct Theme {
+    /// Start from the dark egui visuals instead of the light ones.
+    pub dark: bool,
+    /// Panel and window background.
+    pub background: Rgb,
+    pub text: Rgb,
+    /// Separators and frames.
+    pub border: Rgb,
+    pub button: Rgb,
+    pub button_border: Rgb,
+    /// Toggled buttons and selected values.
+    pub selection: Rgb,
+    pub selection_border: Rgb,
+    /// Text edit and scrollbar background.
+    pub extreme_background: Rgb,
+    /// Text on all the badges but the expired one.
+    pub badge_text: Rgb,
+    /// Red countdowns, under 10 minutes by default.
+    pub urgent: BadgeColors,
+    /// Yellow countdowns, under 20 minutes by default.
+    pub soon: BadgeColors,
+    /// Green countdowns, under 40 minutes by default.
+    pub later: BadgeColors,
+    /// Blue countdowns, also the NEW badge.
+    pub plenty: BadgeColors,
+    /// Void Capture fissures and their notifications.
+    pub void_capture: BadgeColors,
+    pub expired: BadgeColors,
+    pub expired_text: Rgb,
+    /// Invasion durations.
+    pub neutral: BadgeColors,
+}
+
+impl Theme {
+    pub fn light() -> Self {
+        Theme {
+            dark: false,
+            background: [255, 255, 255],
+            text: [0, 0, 0],
+            border: [0, 0, 0],
+            button: [220, 220, 220],
+            button_border: [160, 160, 160],
+            selection: [144, 238, 144],
+            selection_border: [0, 100, 0],
+            extreme_background: [244, 244, 244],
+            badge_text: [0, 0, 0],
+            urgent: BadgeColors {
+                fill: [255, 227, 227],   // Red 1
+                border: [255, 168, 168], // Red 3
+            },
+            soon: BadgeColors {
+                fill: [255, 243, 191],   // Yellow 1
+                border: [255, 224, 102], // Yellow 3
+            },
+            later: BadgeColors {
+                fill: [211, 249, 216],   // Green 1
+                border: [140, 233, 154], // Green 3
+            },
+            plenty: BadgeColors {
+                fill: [208, 235, 255],   // Blue 1
+                border: [116, 192, 252], // Blue 3
+            },
+            void_capture: BadgeColors {
+                fill: [229, 219, 255],   // Violet 1
+                border: [177, 151, 252], // Violet 3
+            },
+            expired: BadgeColors {
+                fill: [42, 42, 42],
+                border: [0, 0, 0],
+            },
+            expired_text: [250, 250, 250],
+            neutral: BadgeColors {
+                fill: [240, 240, 240],
+                border: [200, 200, 200],
+            },
+        }
+    }
+
+    pub fn dark() -> Self {
+        Theme {
+            dark: true,
+            background: [33, 37, 41],    // Gray 9
+            text: [222, 226, 230],       // Gray 3
+            border: [73, 80, 87],        // Gray 7
+            button: [52, 58, 64],        // Gray 8
+            button_border: [73, 80, 87], // Gray 7
+            selection: [43, 138, 62],    // Green 9
+            selection_border: [81, 207, 102],
+            extreme_background: [20, 22, 25],
+            badge_text: [241, 243, 245],
+            urgent: BadgeColors {
+                fill: [130, 30, 30],
+                border: [224, 49, 49], // Red 8
+            },
+            soon: BadgeColors {
+                fill: [110, 80, 10],
+                border: [240, 140, 0], // Yellow 9
+            },
+            later: BadgeColors {
+                fill: [25, 90, 40],
+                border: [47, 158, 68], // Green 8
+            },
+            plenty: BadgeColors {
+                fill: [20, 70, 120],
+                border: [28, 126, 214], // Blue 8
+            },
+            void_capture: BadgeColors {
+                fill: [70, 40, 140],
+                border: [112, 72, 232], // Violet 8
+            },
+            expired: BadgeColors {
+                fill: [0, 0, 0],
+                border: [73, 80, 87],
+            },
+            expired_text: [134, 142, 150],
+            neutral: BadgeColors {
+                fill: [52, 58, 64],
+                border: [73, 80, 87],
+            },
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Theme {
+            dark: true,
+            background: [0, 0, 0],
+            text: [255, 255, 255],
+            border: [255, 255, 255],
+            button: [0, 0, 0],
+            button_border: [255, 255, 255],
+            selection: [255, 255, 0],
+            selection_border: [255, 255, 255],
+            extreme_background: [0, 0, 0],
+            badge_text: [0, 0, 0],
+            urgent: BadgeColors {
+                fill: [255, 80, 80],
+                border: [255, 255, 255],
+            },
+            soon: BadgeColors {
+                fill: [255, 255, 0],
+                border: [255, 255, 255],
+            },
+            later: BadgeColors {
+                fill: [0, 255, 0],
+                border: [255, 255, 255],
+            },
+            plenty: BadgeColors {
+                fill: [0, 200, 255],
+                border: [255, 255, 255],
+            },
+            void_capture: BadgeColors {
+                fill: [255, 0, 255],
+                border: [255, 255, 255],
+            },
+            expired: BadgeColors {
+                fill: [0, 0, 0],
+                border: [255, 255, 255],
+            },
+            expired_text: [255, 255, 255],
+            neutral: BadgeColors {
+                fill: [255, 255, 255],
+                border: [255, 255, 255],
+            },
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+/// Theme the user has picked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub enum ThemeKind {
+    #[default]
+    Light,
+    Dark,
+    HighContrast,
+    /// The colors the user has edited.
+    Custom,
+}
+
+impl ThemeKind {
+    pub const ALL: [ThemeKind; 4] = [
+        ThemeKind::Light,
+        ThemeKind::Dark,
+        ThemeKind::HighContrast,
+        ThemeKind::Custom,
+    ];
+
+    /// Returns the colors of the theme, `custom` for the custom one.
+    pub fn theme(&self, custom: &Theme) -> Theme {
+        match self {
+            ThemeKind::Light => Theme::light(),
+            ThemeKind::Dark => Theme::dark(),
+            ThemeKind::HighContrast => Theme::high_contrast(),
+            ThemeKind::Custom => *custom,
+        }
+    }
+}
+
+impl Display for ThemeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ThemeKind::Light => "Light",
+            ThemeKind::Dark => "Dark",
+            ThemeKind::HighContrast => "High contrast",
+            ThemeKind::Custom => "Custom",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Theme in use, `None` for the light one.
+static THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+/// Use the `theme` for the badges from now on.
+pub fn set(theme: Theme) {
+    *THEME.write() = Some(theme);
+}
+
+/// Returns the theme in use.
+pub fn current() -> Theme {
+    THEME.read().unwrap_or_default()
+}
@@ -0,0 +1,257 @@
+use crate::voidrat::TennoData;
+use parking_lot::RwLock;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+
+/// What the window has to do after a tray menu click.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    /// Show the window if it is hidden, hide it otherwise.
+    ShowHide,
+    /// Start the app again as the full window.
+    LeaveOverlay,
+    Quit,
+}
+
+/// Icon in the system tray with the Cetus cycle and the fissure count in the tooltip.
+///
+/// Only Linux has a tray, the StatusNotifierItem of the desktop.
+pub struct SystemTray {
+    actions: Receiver<TrayAction>,
+    #[cfg(target_os = "linux")]
+    handle: ksni::blocking::Handle<sni::VoidratTray>,
+}
+
+impl SystemTray {
+    /// Add the icon to the tray, `None` if the desktop has no tray.
+    /// Do not disturb and refresh change the `data` right away, other actions wake up the `ctx`.
+    #[cfg(target_os = "linux")]
+    pub fn spawn(data: Arc<RwLock<TennoData>>, ctx: eframe::egui::Context) -> Option<Self> {
+        use ksni::blocking::TrayMethods;
+        use log::warn;
+        use std::sync::mpsc;
+        use std::thread;
+        use std::time::Duration;
+
+        let (tx, actions) = mpsc::channel();
+        let tray = sni::VoidratTray::new(data, ctx, tx);
+        let handle = tray
+            .spawn()
+            .map_err(|e| warn!("Cannot add the tray icon: {}", e))
+            .ok()?;
+
+        // The tooltip only changes when the tray is told to.
+        let handle_clone = handle.clone();
+        thread::spawn(move || {
+            while !handle_clone.is_closed() {
+                handle_clone.update(|tray| tray.refresh());
+                thread::sleep(Duration::from_secs(5));
+            }
+        });
+
+        Some(SystemTray { actions, handle })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn spawn(_data: Arc<RwLock<TennoData>>, _ctx: eframe::egui::Context) -> Option<Self> {
+        None
+    }
+
+    /// Returns the next action the window has to do.
+    pub fn try_recv(&self) -> Option<TrayAction> {
+        self.actions.try_recv().ok()
+    }
+
+    /// Tell the tray if the window is shown, for the show / hide menu item.
+    pub fn set_visible(&self, visible: bool) {
+        #[cfg(target_os = "linux")]
+        self.handle.update(|tray| tray.visible = visible);
+        #[cfg(not(target_os = "linux"))]
+        let _ = visible;
+    }
+
+    /// Tell the tray if the window is the overlay, for the leave overlay menu item.
+    pub fn set_overlay(&self, overlay: bool) {
+        #[cfg(target_os = "linux")]
+        self.handle.update(|tray| tray.overlay = overlay);
+        #[cfg(not(target_os = "linux"))]
+        let _ = overlay;
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sni {
+    use super::TrayAction;
+    use crate::i18n::tr;
+    use crate::util::Resources;
+    use crate::voidrat::TennoData;
+    use ksni::menu::{CheckmarkItem, StandardItem};
+    use ksni::{Icon, MenuItem, ToolTip, Tray};
+    use parking_lot::RwLock;
+    use std::sync::mpsc::Sender;
+    use std::sync::Arc;
+
+    pub struct VoidratTray {
+        data: Arc<RwLock<TennoData>>,
+        /// Wakes up the window for the actions.
+        ctx: eframe::egui::Context,
+        tx: Sender<TrayAction>,
+        icon: Vec<Icon>,
+        /// Tooltip text, updated by `refresh`.
+        summary: String,
+        pub visible: bool,
+        pub overlay: bool,
+    }
+
+    impl VoidratTray {
+        pub fn new(
+            data: Arc<RwLock<TennoData>>,
+            ctx: eframe::egui::Context,
+            tx: Sender<TrayAction>,
+        ) -> Self {
+            let mut tray = VoidratTray {
+                data,
+                ctx,
+                tx,
+                icon: icon(),
+                summary: String::new(),
+                visible: true,
+                overlay: false,
+            };
+            tray.refresh();
+
+            tray
+        }
+
+        /// Summarize the Cetus cycle and the fissures that pass the filters.
+        pub fn refresh(&mut self) {
+            let data = self.data.read();
+            let cycle = if data.cetus_cycle.cetus_is_day() {
+                tr("Day")
+            } else {
+                tr("Night")
+            };
+            let minutes = data.cetus_cycle.cetus_till_cycle().num_minutes().max(0);
+
+            self.summary = format!(
+                "Cetus: {} {}m\n{}: {}",
+                cycle,
+                minutes,
+                tr("Fissures"),
+                data.filtered_fissures().len()
+            );
+        }
+
+        fn send(&self, action: TrayAction) {
+            if self.tx.send(action).is_ok() {
+                self.ctx.request_repaint();
+            }
+        }
+    }
+
+    impl Tray for VoidratTray {
+        fn id(&self) -> String {
+            "voidrat".to_string()
+        }
+
+        fn title(&self) -> String {
+            "Voidrat".to_string()
+        }
+
+        fn icon_pixmap(&self) -> Vec<Icon> {
+            self.icon.clone()
+        }
+
+        fn tool_tip(&self) -> ToolTip {
+            ToolTip {
+                title: "Voidrat".to_string(),
+                description: self.summary.clone(),
+                ..Default::default()
+            }
+        }
+
+        /// Left click shows or hides the window.
+        fn activate(&mut self, _x: i32, _y: i32) {
+            self.send(TrayAction::ShowHide);
+        }
+
+        fn menu(&self) -> Vec<MenuItem<Self>> {
+            let dnd = self.data.read().storage.dnd_active();
+
+            let mut items = vec![];
+            if self.overlay {
+                items.push(
+                    StandardItem {
+                        label: tr("Exit overlay"),
+                        activate: Box::new(|tray: &mut Self| tray.send(TrayAction::LeaveOverlay)),
+                        ..Default::default()
+                    }
+                    .into(),
+                );
+            }
+
+            items.extend([
+                StandardItem {
+                    label: if self.visible { tr("Hide") } else { tr("Show") },
+                    activate: Box::new(|tray: &mut Self| tray.send(TrayAction::ShowHide)),
+                    ..Default::default()
+                }
+                .into(),
+                CheckmarkItem {
+                    label: tr("Do not disturb"),
+                    checked: dnd,
+                    activate: Box::new(|tray: &mut Self| {
+                        let mut data = tray.data.write();
+                        data.storage.dnd_until = if data.storage.dnd_active() {
+                            None
+                        } else {
+                            Some(i64::MAX)
+                        };
+                        data.storage.save();
+                    }),
+                    ..Default::default()
+                }
+                .into(),
+                StandardItem {
+                    label: tr("Refresh now"),
+                    activate: Box::new(|tray: &mut Self| {
+                        tray.data.write().refresh_now();
+                    }),
+                    ..Default::default()
+                }
+                .into(),
+                MenuItem::Separator,
+                StandardItem {
+                    label: tr("Quit"),
+                    activate: Box::new(|tray: &mut Self| tray.send(TrayAction::Quit)),
+                    ..Default::default()
+                }
+                .into(),
+            ]);
+
+            items
+        }
+    }
+
+    /// The app icon in ARGB, as the tray wants it.
+    fn icon() -> Vec<Icon> {
+        let file = Resources::get("icons/voidrat.ico").unwrap().data;
+        let image = match image::load_from_memory(file.as_ref()) {
+            Ok(image) => image.to_rgba8(),
+            Err(_) => return vec![],
+        };
+
+        let (width, height) = image.dimensions();
+        let mut data = image.into_raw();
+        // RGBA to ARGB.
+        for pixel in data.chunks_exact_mut(4) {
+            pixel.rotate_right(1);
+        }
+
+        vec![Icon {
+            width: width as i32,
+            height: height as i32,
+            data,
+        }]
+    }
+}
@@ -0,0 +1,247 @@
+use super::{error_badge, ARCHIVE_ROWS, UI};
+use crate::archive::Archive;
+use crate::i18n::{self, tr};
+use crate::parsers::FissureTier;
+use crate::widgets::UiExt;
+
+use eframe::egui::{
+    Align, ComboBox, Context, Direction, DragValue, Grid, Layout, Pos2, ScrollArea, TextEdit,
+    Window,
+};
+
+use chrono::{Local, TimeZone};
+
+/// Tabs of the archive window.
+#[derive(PartialEq, Clone)]
+pub(super) enum ArchiveView {
+    Fissure,
+    Invasion,
+}
+
+impl UI {
+    /// Open the archive if it is not open yet.
+    pub(super) fn open_archive(&mut self) -> Result<(), String> {
+        if self.archive.is_none() {
+            self.archive = Some(Archive::open().map_err(|e| e.to_string())?);
+        }
+
+        Ok(())
+    }
+
+    /// Run the archive searches again.
+    pub(super) fn search_archive(&mut self) {
+        if let Err(e) = self.open_archive() {
+            self.archive_error = Some(e);
+            return;
+        }
+        let archive = self.archive.as_ref().unwrap();
+
+        let result = archive.fissures(&self.archive_query).and_then(|fissures| {
+            self.archive_fissures = fissures;
+            archive.invasions(&self.archive_reward)
+        });
+
+        match result {
+            Ok(invasions) => {
+                self.archive_invasions = invasions;
+                self.archive_error = None;
+            }
+            Err(e) => self.archive_error = Some(e.to_string()),
+        }
+    }
+
+    pub(super) fn render_archive_window(&mut self, ctx: &Context) {
+        Window::new(tr("History"))
+            .default_width(460.0)
+            .min_width(460.0)
+            .default_pos(Pos2::new(24.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let mut search = false;
+
+                ui.horizontal(|ui| {
+                    ui.toggled_button(&mut self.archive_view, ArchiveView::Fissure, tr("Fissures"));
+                    ui.toggled_button(
+                        &mut self.archive_view,
+                        ArchiveView::Invasion,
+                        tr("Invasions"),
+                    );
+                });
+                ui.add_space(8.0);
+
+                match self.archive_view {
+                    ArchiveView::Fissure => {
+                        Grid::new("archive_fissure_query_grid").show(ui, |ui| {
+                            ui.label(tr("Node"));
+                            search |= ui
+                                .add(
+                                    TextEdit::singleline(&mut self.archive_query.node)
+                                        .hint_text(format!("{} Hepit", tr("e.g."))),
+                                )
+                                .changed();
+                            ui.end_row();
+
+                            ui.label(tr("Mission"));
+                            search |= ui
+                                .add(
+                                    TextEdit::singleline(&mut self.archive_query.mission)
+                                        .hint_text(format!(
+                                            "{} {}",
+                                            tr("e.g."),
+                                            i18n::mission("Capture")
+                                        )),
+                                )
+                                .changed();
+                            ui.end_row();
+
+                            ui.label(tr("Tier"));
+                            let tier_text = self
+                                .archive_query
+                                .tier
+                                .as_ref()
+                                .map_or(tr("Any"), |t| i18n::tier(&t.to_string()));
+                            ComboBox::from_id_source("archive_tier")
+                                .selected_text(tier_text)
+                                .show_ui(ui, |ui| {
+                                    search |= ui
+                                        .selectable_value(
+                                            &mut self.archive_query.tier,
+                                            None,
+                                            tr("Any"),
+                                        )
+                                        .changed();
+                                    for tier in FissureTier::ALL {
+                                        let text = i18n::tier(&tier.to_string());
+                                        search |= ui
+                                            .selectable_value(
+                                                &mut self.archive_query.tier,
+                                                Some(tier),
+                                                text,
+                                            )
+                                            .changed();
+                                    }
+                                });
+                            ui.end_row();
+                        });
+
+                        ui.add_space(8.0);
+                        let fissures = &self.archive_fissures;
+                        match fissures.first() {
+                            Some(last) => ui.label(format!(
+                                "{}: {}, {}: {}",
+                                tr("Seen"),
+                                fissures.len(),
+                                tr("Last seen"),
+                                Local.timestamp(last.activation, 0).format("%Y-%m-%d %H:%M")
+                            )),
+                            None => ui.label(tr("Never seen.")),
+                        };
+
+                        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                            Grid::new("archive_fissure_grid")
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for f in fissures.iter().take(ARCHIVE_ROWS) {
+                                        ui.label(
+                                            Local
+                                                .timestamp(f.activation, 0)
+                                                .format("%Y-%m-%d %H:%M")
+                                                .to_string(),
+                                        );
+                                        ui.label(f.tier.to_string());
+                                        ui.label(&f.mission);
+                                        ui.label(&f.node);
+                                        ui.label(match (f.is_storm, f.hard) {
+                                            (true, _) => tr("Void Storm"),
+                                            (_, true) => tr("Steel Path"),
+                                            _ => String::new(),
+                                        });
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                    ArchiveView::Invasion => {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("Reward"));
+                            search |= ui
+                                .add(
+                                    TextEdit::singleline(&mut self.archive_reward)
+                                        .hint_text(format!("{} Forma", tr("e.g."))),
+                                )
+                                .changed();
+                        });
+
+                        ui.add_space(8.0);
+                        let invasions = &self.archive_invasions;
+                        match invasions.first() {
+                            Some(last) => ui.label(format!(
+                                "{}: {}, {}: {}",
+                                tr("Seen"),
+                                invasions.len(),
+                                tr("Last seen"),
+                                Local.timestamp(last.activation, 0).format("%Y-%m-%d %H:%M")
+                            )),
+                            None => ui.label(tr("Never seen.")),
+                        };
+
+                        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                            Grid::new("archive_invasion_grid")
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for i in invasions.iter().take(ARCHIVE_ROWS) {
+                                        ui.label(
+                                            Local
+                                                .timestamp(i.activation, 0)
+                                                .format("%Y-%m-%d %H:%M")
+                                                .to_string(),
+                                        );
+                                        ui.label(&i.node);
+                                        ui.label(i.rewards());
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                }
+
+                if let Some(e) = &self.archive_error {
+                    error_badge(ui, e);
+                }
+
+                // Retention
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("Keep history for"));
+                    ui.add(
+                        DragValue::new(&mut self.history_retention_days)
+                            .clamp_range(1..=3650)
+                            .suffix(format!(" {}", tr("days"))),
+                    );
+                });
+
+                // Buttons
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_archive = false;
+                        }
+                        if ui.button(tr("Save")).clicked() {
+                            self.app.data.write().storage.history_retention_days =
+                                self.history_retention_days;
+                            self.app.data.write().storage.save();
+                        }
+                        if ui.button(tr("⟳ Refresh")).clicked() {
+                            search = true;
+                        }
+                    },
+                );
+
+                if search {
+                    self.search_archive();
+                }
+            });
+    }
+}
@@ -0,0 +1,111 @@
+use super::UI;
+use crate::countdown::{BadgeColor, Countdowns, Threshold, Thresholds};
+use crate::i18n::tr;
+use std::hash::Hash;
+
+use eframe::egui::{
+    Align, CollapsingHeader, ComboBox, Context, Direction, DragValue, Grid, Layout, Pos2, Window,
+};
+
+/// Render the thresholds of every view, `id` keeps the editors of different windows apart.
+pub(super) fn countdowns_editor(ui: &mut eframe::egui::Ui, id: &str, countdowns: &mut Countdowns) {
+    for (name, thresholds, ring) in [
+        ("Fissures", &mut countdowns.fissures, true),
+        ("Void Storms", &mut countdowns.storms, true),
+        ("Cetus", &mut countdowns.cycles, true),
+        // Invasions have no expiry, the time is how long they have been running.
+        ("Invasions", &mut countdowns.invasions, false),
+    ] {
+        let id = format!("{}_{}", id, name);
+        CollapsingHeader::new(tr(name))
+            .id_source(&id)
+            .show(ui, |ui| thresholds_editor(ui, &id, thresholds, ring));
+    }
+}
+
+/// Render the thresholds of one view, `ring` shows the progress ring option.
+fn thresholds_editor(ui: &mut eframe::egui::Ui, id: &str, thresholds: &mut Thresholds, ring: bool) {
+    let mut remove = None;
+
+    Grid::new((id, "thresholds_grid")).show(ui, |ui| {
+        for (i, threshold) in thresholds.thresholds.iter_mut().enumerate() {
+            ui.label(tr("Under"));
+            ui.add(
+                DragValue::new(&mut threshold.minutes)
+                    .clamp_range(1..=10080)
+                    .suffix(format!(" {}", tr("min"))),
+            );
+            badge_color_combo(ui, (id, i), &mut threshold.color);
+            if ui.button("🗑").clicked() {
+                remove = Some(i);
+            }
+            ui.end_row();
+        }
+
+        ui.label(tr("Otherwise"));
+        ui.label("");
+        badge_color_combo(ui, (id, "otherwise"), &mut thresholds.otherwise);
+        ui.end_row();
+    });
+
+    if let Some(i) = remove {
+        thresholds.thresholds.remove(i);
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button(tr("Add threshold")).clicked() {
+            let minutes = thresholds.thresholds.last().map_or(10, |t| t.minutes * 2);
+            thresholds.thresholds.push(Threshold {
+                minutes,
+                color: thresholds.otherwise,
+            });
+        }
+        if ring {
+            ui.checkbox(&mut thresholds.progress_ring, tr("Progress ring"));
+        }
+    });
+}
+
+fn badge_color_combo(ui: &mut eframe::egui::Ui, id: impl Hash, value: &mut BadgeColor) {
+    ComboBox::from_id_source(id)
+        .selected_text(tr(&value.to_string()))
+        .show_ui(ui, |ui| {
+            for color in BadgeColor::ALL {
+                ui.selectable_value(value, color, tr(&color.to_string()));
+            }
+        });
+}
+
+impl UI {
+    /// Render the countdown color thresholds of every view.
+    pub(super) fn render_countdowns_window(&mut self, ctx: &Context) {
+        Window::new(tr("Countdown colors"))
+            .default_width(320.0)
+            .min_width(320.0)
+            .default_pos(Pos2::new(24.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                countdowns_editor(ui, "countdowns", &mut self.countdowns);
+
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_countdowns = false;
+                        }
+                        if ui.button(tr("Save")).clicked() {
+                            self.countdowns.sort();
+                            let mut data = self.app.data.write();
+                            data.storage.countdowns = self.countdowns.clone();
+                            data.storage.save();
+                            self.show_countdowns = false;
+                        }
+                        if ui.button(tr("Defaults")).clicked() {
+                            self.countdowns = Countdowns::default();
+                        }
+                    },
+                );
+            });
+    }
+}
@@ -0,0 +1,145 @@
+use super::UI;
+use crate::drop_table::{DROP_TABLE_URL, VAULTED_URL};
+use crate::i18n::{self, tr};
+use crate::parsers::FissureTier;
+use crate::widgets::UiExt;
+
+use eframe::egui::{
+    Align, CollapsingHeader, Context, Direction, Grid, Layout, Pos2, ScrollArea, TextEdit, Window,
+};
+
+use std::thread;
+
+impl UI {
+    pub(super) fn render_drops_window(&mut self, ctx: &Context) {
+        Window::new(tr("Relic drops"))
+            .default_width(420.0)
+            .min_width(420.0)
+            .default_pos(Pos2::new(40.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let table = self.drop_table.read().clone();
+
+                ui.horizontal(|ui| {
+                    ui.label("🔍");
+                    ui.add(
+                        TextEdit::singleline(&mut self.drops_query)
+                            .hint_text(tr("Which relic drops...")),
+                    );
+                });
+                ui.add_space(8.0);
+
+                if !self.drops_query.trim().is_empty() {
+                    let results = table.search(&self.drops_query);
+                    if results.is_empty() {
+                        ui.label(tr("No relic drops that."));
+                    }
+
+                    ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                        Grid::new("relic_drops_search_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (relic, reward) in results {
+                                    ui.label(relic.full_name());
+                                    ui.label(&reward.item_name);
+                                    ui.label(&reward.rarity);
+                                    ui.label(format!("{:.2}%", reward.chance));
+                                    ui.label(relic.vaulted_text());
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                } else {
+                    ui.horizontal(|ui| {
+                        for tier in FissureTier::ALL {
+                            let text = i18n::tier(&tier.to_string());
+                            ui.toggled_button(&mut self.drops_tier, tier, text);
+                        }
+                    });
+                    ui.add_space(8.0);
+
+                    ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                        let mut empty = true;
+                        for relic in table.relics_for_tier(&self.drops_tier) {
+                            empty = false;
+                            let title = format!("{}  {}", relic.full_name(), relic.vaulted_text());
+
+                            CollapsingHeader::new(title).show(ui, |ui| {
+                                Grid::new(("relic_drops_grid", relic.full_name()))
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        for reward in &relic.rewards {
+                                            ui.label(&reward.item_name);
+                                            ui.label(&reward.rarity);
+                                            ui.label(format!("{:.2}%", reward.chance));
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                        }
+                        if empty {
+                            ui.label(format!(
+                                "{}: {}",
+                                tr("No relics known"),
+                                i18n::tier(&self.drops_tier.to_string())
+                            ));
+                        }
+                    });
+                }
+
+                // Data source and update
+                ui.add_space(8.0);
+                ui.label(format!(
+                    "{}: {}, {}: {}",
+                    tr("Relics"),
+                    table.relics.len(),
+                    tr("Source"),
+                    if table.source.is_empty() {
+                        tr("unknown")
+                    } else {
+                        table.source.clone()
+                    }
+                ));
+                if let Some(status) = self.drop_table_status.read().as_ref() {
+                    ui.label(status);
+                } else if table.is_sample() {
+                    ui.weak(tr(
+                        "Only a few relics are bundled, update to download all of them",
+                    ));
+                }
+
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_drops = false;
+                        }
+                        if ui.button(tr("⬇ Update")).clicked() {
+                            self.update_drop_table();
+                        }
+                    },
+                );
+            });
+    }
+
+    /// Download the full drop table in the background.
+    fn update_drop_table(&self) {
+        *self.drop_table_status.write() = Some(tr("Updating..."));
+
+        let drop_table = self.drop_table.clone();
+        let status = self.drop_table_status.clone();
+        let timeout = self.app.data.read().storage.request_timeout;
+        thread::spawn(move || {
+            let mut table = drop_table.read().clone();
+
+            *status.write() = Some(match table.update(DROP_TABLE_URL, VAULTED_URL, timeout) {
+                Ok(count) => {
+                    *drop_table.write() = table;
+                    format!("{}: {}", tr("Updated relics"), count)
+                }
+                Err(e) => format!("{}: {}", tr("Update failed"), e),
+            });
+        });
+    }
+}
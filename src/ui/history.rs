@@ -0,0 +1,141 @@
+use super::UI;
+use crate::i18n::tr;
+
+use eframe::egui::{
+    Align, Button, Context, Direction, DragValue, Grid, Layout, Pos2, ScrollArea, Window,
+};
+
+use chrono::{Local, TimeZone};
+
+impl UI {
+    pub(super) fn render_history_window(&mut self, ctx: &Context) {
+        Window::new(tr("Notification history"))
+            .default_width(460.0)
+            .min_width(460.0)
+            .default_pos(Pos2::new(24.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let mut changed = false;
+                let history = self.app.data.read().storage.notifications.clone();
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("Snooze for"));
+                    ui.add(
+                        DragValue::new(&mut self.snooze_minutes)
+                            .clamp_range(1..=240)
+                            .suffix(format!(" {}", tr("min"))),
+                    );
+                });
+                ui.add_space(8.0);
+
+                if history.entries.is_empty() {
+                    ui.label(tr("No notifications yet."));
+                }
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    Grid::new("notification_history_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            // Newest first.
+                            for n in history.entries.iter().rev() {
+                                ui.label(
+                                    Local.timestamp(n.fired_at, 0).format("%H:%M").to_string(),
+                                );
+                                ui.label(tr(&n.rule.to_string()));
+                                ui.label(n.key.to_string());
+
+                                let state = if n.dismissed {
+                                    tr("Dismissed")
+                                } else if let Some(until) = n.snoozed_until {
+                                    format!(
+                                        "{} {}",
+                                        tr("Snoozed till"),
+                                        Local.timestamp(until, 0).format("%H:%M")
+                                    )
+                                } else if !n.is_active() {
+                                    tr("Expired")
+                                } else {
+                                    tr("Active")
+                                };
+                                ui.label(state);
+
+                                ui.horizontal(|ui| {
+                                    if ui
+                                        .add_enabled(
+                                            n.is_active() && !n.dismissed,
+                                            Button::new("💤"),
+                                        )
+                                        .on_hover_text(tr("Snooze"))
+                                        .clicked()
+                                    {
+                                        self.app.data.write().storage.notifications.snooze(
+                                            n.rule,
+                                            &n.key,
+                                            self.snooze_minutes,
+                                        );
+                                        changed = true;
+                                    }
+                                    if ui
+                                        .add_enabled(!n.dismissed, Button::new("✖"))
+                                        .on_hover_text(tr("Dismiss"))
+                                        .clicked()
+                                    {
+                                        self.app
+                                            .data
+                                            .write()
+                                            .storage
+                                            .notifications
+                                            .dismiss(n.rule, &n.key);
+                                        changed = true;
+                                    }
+                                    if ui
+                                        .add_enabled(!history.is_muted(n.rule), Button::new("🔕"))
+                                        .on_hover_text(tr("Mute this rule until reset"))
+                                        .clicked()
+                                    {
+                                        self.app.data.write().storage.notifications.mute(n.rule);
+                                        changed = true;
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                if !history.muted.is_empty() {
+                    ui.add_space(8.0);
+                    ui.heading(tr("Muted rules"));
+
+                    for (rule, until) in &history.muted {
+                        ui.horizontal(|ui| {
+                            ui.label(format!(
+                                "{} {} {}",
+                                tr(&rule.to_string()),
+                                tr("until"),
+                                Local.timestamp(*until, 0).format("%Y-%m-%d %H:%M")
+                            ));
+                            if ui.button(tr("Unmute")).clicked() {
+                                self.app.data.write().storage.notifications.unmute(*rule);
+                                changed = true;
+                            }
+                        });
+                    }
+                }
+
+                ui.add_space(8.0);
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_history = false;
+                        }
+                    },
+                );
+
+                if changed {
+                    self.app.data.write().storage.save();
+                }
+            });
+    }
+}
@@ -0,0 +1,2129 @@
+mod archive;
+mod countdowns;
+mod drops;
+mod history;
+mod notifications;
+mod palette;
+mod relics;
+mod replay_bar;
+mod settings;
+mod shortcuts;
+mod stats;
+mod theme_editor;
+
+use archive::ArchiveView;
+use settings::SettingsTab;
+use stats::StatsView;
+
+use crate::archive::{Archive, FissureQuery, FissureRecord, InvasionRecord};
+use crate::clock;
+use crate::countdown::{Countdowns, Thresholds};
+use crate::data_pack::DataPack;
+use crate::drop_table::DropTable;
+use crate::export::{export, ExportFormat};
+use crate::filters::{set_shown, FilterOptions, FissureFilter};
+use crate::i18n::{self, tr, Language};
+use crate::keybindings::{Action, KeyBinding, Keybindings};
+use crate::notifications::{NotificationKey, NotificationSettings};
+use crate::overlay::{Overlay, OVERLAY_WIDTH};
+use crate::parsers::{Fissure, FissureTier, Invasion, SolarNode, SolarNodes};
+use crate::relics::RelicInventory;
+use crate::search::{fissure_score, invasion_score, search, SearchHit};
+use crate::sorting::{group, GroupBy, SortKey, TableSorting};
+use crate::stats::Stats;
+use crate::theme::{self, Theme, ThemeKind};
+use crate::tray::{SystemTray, TrayAction};
+use crate::util::{duration_to_string, get_retained_image};
+use crate::voidrat::{Options, Storage, VoidRat};
+use crate::widgets::UiExt;
+use eframe::egui::style::WidgetVisuals;
+use std::collections::HashMap;
+
+use eframe::egui::style::Margin;
+use eframe::egui::{
+    menu, Align, Button, CentralPanel, CollapsingHeader, Color32, ColorImage, ComboBox, Context,
+    Direction, Frame, Grid, Key, Label, Layout, Pos2, Response, Rgba, RichText, Rounding,
+    ScrollArea, Sense, Separator, Slider, Stroke, TextEdit, TextStyle, TopBottomPanel, Vec2,
+    Visuals, Widget, Window,
+};
+use egui_extras::{Column, RetainedImage, TableBuilder};
+
+use chrono::{Duration, Local, TimeZone};
+use log::warn;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::{process, thread};
+
+/// How many archived rows are listed at most.
+const ARCHIVE_ROWS: usize = 100;
+
+const LOADING_FRAMES: [&str; 4] = ["Loading", "Loading.", "Loading..", "Loading..."];
+
+/// Do not disturb durations in minutes, `None` lasts until turned off.
+const DND_DURATIONS: [(&str, Option<i64>); 5] = [
+    ("30 minutes", Some(30)),
+    ("1 hour", Some(60)),
+    ("2 hours", Some(120)),
+    ("8 hours", Some(480)),
+    ("Until turned off", None),
+];
+
+/// How many matches the search shows below the search box.
+const SEARCH_RESULTS: usize = 8;
+
+/// State shared by the rows of the fissure tables, changes are applied after rendering.
+struct FissureRows {
+    watched: Vec<NotificationKey>,
+    /// Get the NEW badge.
+    unseen: Vec<NotificationKey>,
+    relics: RelicInventory,
+    /// Tier whose drop table was clicked.
+    open_drops: Option<FissureTier>,
+    /// Fissure whose reminder was toggled.
+    toggle_watched: Option<NotificationKey>,
+    /// Badge colors of the countdowns.
+    countdown: Thresholds,
+}
+
+#[derive(PartialEq, Clone)]
+enum ActiveView {
+    Fissure,
+    VoidStorm,
+    Invasion,
+}
+
+/// Crude animated text thing that shows one "frame" every 250ms.
+struct AnimatedText {
+    frames: Vec<String>,
+    current_frame: usize,
+    time: i64,
+}
+
+impl AnimatedText {
+    pub fn new(frames: &[&str]) -> Self {
+        AnimatedText {
+            frames: frames
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>(),
+            current_frame: 0,
+            time: Local::now().timestamp_millis(),
+        }
+    }
+
+    /// Returns the correct frame that should be rendered.
+    pub fn animate(&mut self) -> &str {
+        // Set the current frame to 0 if it is too large.
+        if self.current_frame >= self.frames.len() {
+            self.current_frame = 0;
+        }
+
+        // Get the correct text that should be returned.
+        let text = self.frames.get(self.current_frame).unwrap().as_ref();
+
+        // Increment current frame and update the time value.
+        if Local::now().timestamp_millis() >= self.time + 250 {
+            self.current_frame += 1;
+            self.time = Local::now().timestamp_millis();
+        }
+
+        // Return text.
+        text
+    }
+}
+
+/// Images for the UI.
+struct Images {
+    missing: RetainedImage,
+    lith: RetainedImage,
+    meso: RetainedImage,
+    neo: RetainedImage,
+    axi: RetainedImage,
+    requiem: RetainedImage,
+    steel_essence: RetainedImage,
+
+    invasion: HashMap<String, RetainedImage>,
+}
+
+impl Default for Images {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Images {
+    fn new() -> Self {
+        Images {
+            missing: Self::dummy(),
+            lith: Self::dummy(),
+            meso: Self::dummy(),
+            neo: Self::dummy(),
+            axi: Self::dummy(),
+            requiem: Self::dummy(),
+            steel_essence: Self::dummy(),
+
+            invasion: HashMap::new(),
+        }
+    }
+
+    /// Return 1x1 px dummy `RetainedImage`.
+    fn dummy() -> RetainedImage {
+        RetainedImage::from_color_image("dummy", ColorImage::new([1, 1], Color32::BLACK))
+    }
+
+    pub fn get_invasion_img(&self, key: &str) -> &RetainedImage {
+        if let Some(img) = self.invasion.get(key) {
+            img
+        } else {
+            self.invasion
+                .iter()
+                .find_map(|(k, v)| if key.contains(k) { Some(v) } else { None })
+                .unwrap_or(&self.missing)
+        }
+    }
+}
+
+const INVASION_REWARDS: [&str; 15] = [
+    "Fieldron",
+    "Detonite Injector",
+    "Mutagen Mass",
+    "Infested Alad V Nav Coordinate",
+    "Karak Wraith",
+    "Latron Wraith",
+    "Strun Wraith",
+    "Twin Vipers Wraith",
+    "Sheev",
+    "Dera Vandal",
+    "Snipetron Vandal",
+    "Orokin Catalyst Blueprint",
+    "Orokin Reactor Blueprint",
+    "Forma Blueprint",
+    "Exilus Warframe Adapter Blueprint",
+];
+
+pub struct UI {
+    /// The actual app where UI get its data from.
+    app: VoidRat,
+    /// True after the data has been loaded.
+    initialized: bool,
+    /// Loading text to show before initializing is done.
+    loading_text: AnimatedText,
+    /// All the images UI uses.
+    images: Arc<RwLock<Images>>,
+    /// Currently active view.
+    active_view: ActiveView,
+    /// Render the notification window when true.
+    show_notifications: bool,
+    /// For notification window editing state
+    notification_settings: NotificationSettings,
+    /// Show fissure filters.
+    show_filters: bool,
+    /// Name for the next saved filter preset.
+    filter_preset_name: String,
+    /// Search that filters the active view.
+    search_query: String,
+    /// Render the notification history window when true.
+    show_history: bool,
+    /// How many minutes the snooze button in the history window snoozes for.
+    snooze_minutes: i64,
+    /// Render the relic inventory window when true.
+    show_relics: bool,
+    /// For relic inventory editing state
+    relic_inventory: RelicInventory,
+    /// Path of the file to import relics from.
+    relic_import_path: String,
+    /// Result of the last relic import.
+    relic_import_status: Option<String>,
+    /// Render the relic drops window when true.
+    show_drops: bool,
+    /// Relic tier listed in the relic drops window.
+    drops_tier: FissureTier,
+    /// Reward search in the relic drops window.
+    drops_query: String,
+    /// Rewards for all the relics.
+    drop_table: Arc<RwLock<DropTable>>,
+    /// Result of the last drop table update.
+    drop_table_status: Arc<RwLock<Option<String>>>,
+    /// Render the archive window when true.
+    show_archive: bool,
+    /// Active tab of the archive window.
+    archive_view: ArchiveView,
+    /// Opened when the archive window is first shown.
+    archive: Option<Archive>,
+    /// Fissure search in the archive window.
+    archive_query: FissureQuery,
+    /// Invasion reward search in the archive window.
+    archive_reward: String,
+    /// Results of the last archive search.
+    archive_fissures: Vec<FissureRecord>,
+    /// Results of the last archive search.
+    archive_invasions: Vec<InvasionRecord>,
+    /// Error from the last archive search.
+    archive_error: Option<String>,
+    /// For archive retention editing state
+    history_retention_days: u32,
+    /// Render the statistics window when true.
+    show_stats: bool,
+    /// Active tab of the statistics window.
+    stats_view: StatsView,
+    /// Tier shown in the mission distribution tab.
+    stats_tier: FissureTier,
+    /// Collected when the statistics window is opened.
+    stats: Stats,
+    /// Result of the last statistics collection or export.
+    stats_status: Option<String>,
+    /// Result of the last export from the file menu.
+    export_status: Option<String>,
+    /// Render the about window when true.
+    show_about: bool,
+    /// Render the command palette when true.
+    show_palette: bool,
+    palette_query: String,
+    /// Index of the highlighted command, Enter runs it.
+    palette_selected: usize,
+    /// Render the keyboard shortcuts window when true.
+    show_shortcuts: bool,
+    /// Action waiting for a key press to bind it.
+    capturing: Option<Action>,
+    /// Focus the search box on the next frame.
+    focus_search: bool,
+    /// View whose items are marked seen when another view is shown.
+    shown_view: ActiveView,
+    /// Render the custom theme window when true.
+    show_theme_editor: bool,
+    /// Custom theme being edited.
+    custom_theme: Theme,
+    /// Render the countdown colors window when true.
+    show_countdowns: bool,
+    /// Countdown colors being edited.
+    countdowns: Countdowns,
+    /// The window was started as the overlay, the window options cannot change afterwards.
+    overlay: bool,
+    /// The overlay was started click-through, only the tray or closing the window can leave it.
+    click_through: bool,
+    /// Switch between the overlay and the full window on the next frame.
+    toggle_overlay: bool,
+    /// Tray icon, `None` if the desktop has no tray.
+    tray: Option<SystemTray>,
+    /// The window is shown, it is only hidden to the tray.
+    visible: bool,
+    /// Hide the window on the next frame.
+    hide: bool,
+    /// Really close the window instead of hiding it to the tray.
+    quitting: bool,
+    /// Data pack url in the about window.
+    data_pack_url: String,
+    /// Render the settings window when true.
+    show_settings: bool,
+    settings_tab: SettingsTab,
+    /// Settings being edited, applied to the storage only when they are valid.
+    settings: Storage,
+    /// Settings the editing started from, only the options changed since then are applied.
+    settings_opened: Storage,
+    /// Validation error or result of the last apply.
+    settings_status: Option<String>,
+    /// Result of the last data pack update.
+    data_pack_status: Arc<RwLock<Option<String>>>,
+}
+
+impl UI {
+    pub(crate) fn new(
+        cc: &eframe::CreationContext<'_>,
+        options: Options,
+        storage: Storage,
+    ) -> Self {
+        // Dummo images for now.
+        let images = Arc::new(RwLock::new(Images::default()));
+
+        // Load fissure images in a separate thread. Dummy images begone.
+        let images_clone = images.clone();
+        thread::spawn(move || {
+            images_clone.write().missing = get_retained_image("MissingImg.webp");
+            images_clone.write().lith = get_retained_image("VoidProjectionsIronD.webp");
+            images_clone.write().meso = get_retained_image("VoidProjectionsBronzeD.webp");
+            images_clone.write().neo = get_retained_image("VoidProjectionsSilverD.webp");
+            images_clone.write().axi = get_retained_image("VoidProjectionsGoldD.webp");
+            images_clone.write().requiem = get_retained_image("RequiemR0.webp");
+            images_clone.write().steel_essence = get_retained_image("SteelEssence.webp");
+
+            let mut invasion_imgs: HashMap<String, RetainedImage> = HashMap::new();
+            for ir in INVASION_REWARDS {
+                let img_name = format!("{}.webp", ir.replace(' ', ""));
+                invasion_imgs.insert(ir.to_owned(), get_retained_image(&img_name));
+            }
+
+            images_clone.write().invasion = invasion_imgs;
+        });
+
+        let overlay = storage.overlay.enabled;
+        let click_through = overlay && options.click_through;
+        let app = VoidRat::new(options, storage);
+        let tray = SystemTray::spawn(app.data.clone(), cc.egui_ctx.clone());
+        if let Some(tray) = &tray {
+            tray.set_overlay(overlay);
+        }
+
+        let data_clone = app.data.read().clone();
+        i18n::set_language(data_clone.storage.language);
+
+        ui_style(
+            &cc.egui_ctx,
+            data_clone
+                .storage
+                .theme
+                .theme(&data_clone.storage.custom_theme),
+        );
+
+        UI {
+            app,
+            initialized: false,
+            loading_text: AnimatedText::new(&LOADING_FRAMES),
+            images,
+            active_view: ActiveView::Fissure,
+            show_notifications: false,
+            notification_settings: data_clone.storage.notification_settings,
+            show_filters: false,
+            filter_preset_name: String::new(),
+            search_query: String::new(),
+            show_history: false,
+            snooze_minutes: 15,
+            show_relics: false,
+            relic_inventory: data_clone.relics,
+            relic_import_path: String::new(),
+            relic_import_status: None,
+            show_drops: false,
+            drops_tier: FissureTier::Lith,
+            drops_query: String::new(),
+            drop_table: Arc::new(RwLock::new(DropTable::load())),
+            drop_table_status: Arc::new(RwLock::new(None)),
+            show_archive: false,
+            archive_view: ArchiveView::Fissure,
+            archive: None,
+            archive_query: FissureQuery::default(),
+            archive_reward: String::new(),
+            archive_fissures: vec![],
+            archive_invasions: vec![],
+            archive_error: None,
+            history_retention_days: data_clone.storage.history_retention_days,
+            show_stats: false,
+            stats_view: StatsView::Tiers,
+            stats_tier: FissureTier::Lith,
+            stats: Stats::default(),
+            stats_status: None,
+            export_status: None,
+            show_about: false,
+            show_palette: false,
+            palette_query: String::new(),
+            palette_selected: 0,
+            show_shortcuts: false,
+            capturing: None,
+            focus_search: false,
+            shown_view: ActiveView::Fissure,
+            show_theme_editor: false,
+            custom_theme: data_clone.storage.custom_theme,
+            show_countdowns: false,
+            countdowns: data_clone.storage.countdowns.clone(),
+            overlay,
+            click_through,
+            toggle_overlay: false,
+            // Starting minimized without a tray would leave no way to show the window.
+            hide: data_clone.storage.start_minimized && tray.is_some(),
+            tray,
+            visible: true,
+            quitting: false,
+            data_pack_url: data_clone.storage.data_pack_url.clone(),
+            data_pack_status: Arc::new(RwLock::new(None)),
+            show_settings: false,
+            settings_tab: SettingsTab::General,
+            settings: Storage::default(),
+            settings_opened: Storage::default(),
+            settings_status: None,
+        }
+    }
+
+    /// Render all incomplete invasions.
+    fn render_invasions(&mut self, ctx: &Context, ui: &mut eframe::egui::Ui) {
+        let (sorting, mut invasions, unseen, countdown) = {
+            let data = self.app.data.read();
+
+            (
+                data.storage.invasion_sorting,
+                data.invasions
+                    .iter()
+                    .filter(|i| invasion_score(&self.search_query, i).is_some())
+                    .cloned()
+                    .collect::<Vec<Invasion>>(),
+                data.unseen.clone(),
+                data.storage.countdowns.invasions.clone(),
+            )
+        };
+        sorting.sort_invasions(&mut invasions);
+        let groups = group(invasions, |i| sorting.invasion_group(i));
+
+        // Column headers, clicking sorts by the column.
+        let mut new_sorting = sorting;
+        invasion_columns(TableBuilder::new(ui))
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    group_menu(ui, &mut new_sorting.group, &[GroupBy::Planet]);
+                });
+                header.col(|_| {});
+                header.col(|_| {});
+                header.col(|ui| {
+                    ui.horizontal(|ui| {
+                        sort_header(ui, &mut new_sorting, SortKey::Node, "Node");
+                        sort_header(ui, &mut new_sorting, SortKey::Planet, "Planet");
+                        sort_header(ui, &mut new_sorting, SortKey::Time, "Time");
+                    });
+                });
+            })
+            .body(|_| {});
+
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for (i, (name, invasions)) in groups.iter().enumerate() {
+                    ui.push_id(i, |ui| match name {
+                        Some(name) => {
+                            CollapsingHeader::new(format!("{} ({})", name, invasions.len()))
+                                .id_source(name)
+                                .default_open(true)
+                                .show(ui, |ui| {
+                                    self.invasion_table(ctx, ui, invasions, &unseen, &countdown)
+                                });
+                        }
+                        None => self.invasion_table(ctx, ui, invasions, &unseen, &countdown),
+                    });
+                }
+            });
+
+        if new_sorting != sorting {
+            let mut data = self.app.data.write();
+            data.storage.invasion_sorting = new_sorting;
+            data.storage.save();
+        }
+    }
+
+    /// Render a table with the `invasions`.
+    fn invasion_table(
+        &self,
+        ctx: &Context,
+        ui: &mut eframe::egui::Ui,
+        invasions: &[Invasion],
+        unseen: &[NotificationKey],
+        countdown: &Thresholds,
+    ) {
+        invasion_columns(TableBuilder::new(ui)).body(|mut body| {
+            for invasion in invasions {
+                body.row(120.0, |mut row| {
+                    ctx.request_repaint();
+
+                    // 1st column.
+                    row.col(|ui| {
+                        ui.horizontal(|ui| {
+                            for reward in &invasion.rewards.defender {
+                                ui.with_layout(
+                                    Layout::from_main_dir_and_cross_align(
+                                        Direction::TopDown,
+                                        Align::Center,
+                                    ),
+                                    |ui| {
+                                        ui.image(
+                                            self.images
+                                                .read()
+                                                .get_invasion_img(&reward.item)
+                                                .texture_id(ctx),
+                                            self.images
+                                                .read()
+                                                .get_invasion_img(&reward.item)
+                                                .size_vec2()
+                                                * 0.5,
+                                        );
+                                        ui.label(i18n::reward(reward));
+                                    },
+                                );
+                            }
+                        });
+                    });
+                    // 2nd column.
+                    row.col(|ui| {
+                        if !invasion.rewards.attacker.is_empty()
+                            && !invasion.rewards.defender.is_empty()
+                        {
+                            ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
+                                ui.add_space(30.0);
+                                ui.separator();
+                            });
+                            // ui.horizontal(|ui| {
+                            //     ui.separator();
+                            // });
+                        }
+                    });
+                    // 3rd column.
+                    row.col(|ui| {
+                        for reward in &invasion.rewards.attacker {
+                            ui.horizontal(|ui| {
+                                ui.with_layout(
+                                    Layout::from_main_dir_and_cross_align(
+                                        Direction::TopDown,
+                                        Align::Center,
+                                    ),
+                                    |ui| {
+                                        ui.image(
+                                            self.images
+                                                .read()
+                                                .get_invasion_img(&reward.item)
+                                                .texture_id(ctx),
+                                            self.images
+                                                .read()
+                                                .get_invasion_img(&reward.item)
+                                                .size_vec2()
+                                                * 0.5,
+                                        );
+                                        ui.label(i18n::reward(reward));
+                                    },
+                                );
+                            });
+                        }
+                    });
+                    // 4nd column.
+                    row.col(|ui| {
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            ui.label(i18n::node(&invasion.node))
+                                .on_hover_text(node_details(&invasion.node));
+                            if let Some(levels) = invasion.node.level_range() {
+                                ui.weak(levels);
+                            }
+                            if unseen.contains(&invasion.notification_key()) {
+                                ui.new_badge();
+                            }
+                        });
+                        ui.add_space(4.0);
+                        let (fill, border) = countdown.colors(&invasion.active_duration()).colors();
+                        ui.badge_frame(fill, border, |ui| {
+                            ui.colored_label(
+                                theme::color(theme::current().badge_text),
+                                RichText::new(duration_to_string(&invasion.active_duration()))
+                                    .text_style(TextStyle::Monospace),
+                            );
+                        });
+                    });
+                });
+            }
+        });
+    }
+
+    /// Render the Cetus day or night with the time left.
+    fn render_cetus(&self, ui: &mut eframe::egui::Ui) {
+        let cetus_text = if self.app.data.read().cetus_cycle.cetus_is_day() {
+            format!("{} ☀", tr("Cetus")) // Day
+        } else {
+            format!("{} 🌙", tr("Cetus")) // Night
+        };
+
+        ui.heading(cetus_text);
+
+        // Duration of the current cycle.
+        let (cetus_cycle, progress, countdown) = {
+            let data = self.app.data.read();
+
+            (
+                data.cetus_cycle.cetus_till_cycle(),
+                data.cetus_cycle.progress(),
+                data.storage.countdowns.cycles.clone(),
+            )
+        };
+        // Badge text.
+        let text = duration_to_string(&cetus_cycle);
+        // Badge fill and border color.
+        let (bg_color, border_color) = countdown.colors(&cetus_cycle).colors();
+
+        if cetus_cycle.num_seconds() > 0 {
+            // Current cycle is ongoing.
+            ui.badge_frame(bg_color, border_color, |ui| {
+                countdown_label(ui, text, countdown.progress_ring.then_some(progress));
+            });
+        } else {
+            // Current cycle has expired.
+            let (fill, border) = theme::current().expired.colors();
+            ui.badge_frame(fill, border, |ui| {
+                ui.colored_label(
+                    theme::color(theme::current().expired_text),
+                    RichText::new(tr("Expired")).text_style(TextStyle::Monospace),
+                );
+            });
+        }
+    }
+
+    fn keybindings(&self) -> Keybindings {
+        self.app.data.read().storage.keybindings.clone()
+    }
+
+    /// Run the actions of the pressed keys, or bind the pressed key to the action waiting for one.
+    fn handle_keys(&mut self, ctx: &Context) {
+        if let Some(action) = self.capturing {
+            let input = ctx.input();
+            if input.key_pressed(Key::Escape) {
+                self.capturing = None;
+            } else if let Some(binding) = input.events.iter().find_map(KeyBinding::from_event) {
+                let mut data = self.app.data.write();
+                data.storage.keybindings.set(action, Some(binding));
+                data.storage.save();
+                self.capturing = None;
+            }
+            return;
+        }
+
+        // Not in the same statement as `ctx.input()`, both lock the context.
+        let typing = ctx.wants_keyboard_input();
+        let actions = self.keybindings().pressed(&ctx.input(), typing);
+        for action in actions {
+            self.run_action(action);
+        }
+    }
+
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::ShowFissures => self.active_view = ActiveView::Fissure,
+            Action::ShowVoidStorms => self.active_view = ActiveView::VoidStorm,
+            Action::ShowInvasions => self.active_view = ActiveView::Invasion,
+            Action::ToggleFilters => self.show_filters = !self.show_filters,
+            Action::Search => self.focus_search = true,
+            Action::OpenNotifications => self.show_notifications = !self.show_notifications,
+            Action::OpenHistory => self.show_history = !self.show_history,
+            Action::RefreshNow => {
+                self.app.data.write().refresh_now();
+            }
+            Action::ToggleOverlay => self.toggle_overlay = true,
+            Action::CommandPalette => {
+                self.show_palette = !self.show_palette;
+                self.palette_query.clear();
+                self.palette_selected = 0;
+            }
+            Action::ShowShortcuts => self.show_shortcuts = !self.show_shortcuts,
+            Action::OpenSettings => self.open_settings(),
+        }
+    }
+
+    /// Render the last and the next update, where the data came from and how long the fetch took,
+    /// with a button to refresh now.
+    fn render_status_bar(&mut self, ctx: &Context) {
+        let (status, last_update, next_update, refresh_wait) = {
+            let data = self.app.data.read();
+
+            (
+                data.update_status.clone(),
+                data.storage.last_update,
+                data.storage.next_update(),
+                data.storage.refresh_wait(),
+            )
+        };
+
+        TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let refresh = ui
+                    .add_enabled(
+                        self.app.replay.is_none() && !status.updating && refresh_wait == 0,
+                        Button::new("⟳").small(),
+                    )
+                    .on_hover_text(tr("Refresh now"))
+                    .on_disabled_hover_text(format!(
+                        "{} {}s",
+                        tr("Next refresh possible in"),
+                        refresh_wait
+                    ));
+                if refresh.clicked() {
+                    self.app.data.write().refresh_now();
+                }
+
+                if self.app.replay.is_some() {
+                    ui.weak(tr("Replaying snapshots"));
+                    return;
+                }
+
+                if status.updating {
+                    ui.spinner();
+                    ui.weak(tr("Updating…"));
+                } else if last_update > 0 {
+                    ui.weak(format!(
+                        "{} {}",
+                        tr("Updated"),
+                        Local.timestamp(last_update, 0).format("%H:%M:%S")
+                    ));
+                    ui.weak("·");
+                    ui.weak(format!(
+                        "{} {}",
+                        tr("Next in"),
+                        duration_to_string(&Duration::seconds(next_update.max(0)))
+                    ));
+                }
+
+                if status.failed {
+                    ui.weak("·");
+                    error_badge(ui, tr("Update failed"));
+                }
+
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if let Some(duration) = status.duration {
+                        ui.weak(format!("{:.1}s", duration.as_secs_f32()));
+                        ui.weak("·");
+                    }
+                    if let Some(source) = status.source {
+                        ui.weak(tr(&source.to_string()));
+                    }
+                });
+            });
+        });
+    }
+
+    /// Do the actions picked from the tray menu.
+    fn handle_tray(&mut self, frame: &mut eframe::Frame) {
+        let mut show_hide = std::mem::take(&mut self.hide) && self.visible;
+
+        if let Some(tray) = &self.tray {
+            while let Some(action) = tray.try_recv() {
+                match action {
+                    TrayAction::ShowHide => show_hide = !show_hide,
+                    TrayAction::LeaveOverlay => self.toggle_overlay = self.overlay,
+                    TrayAction::Quit => {
+                        self.quitting = true;
+                        frame.close();
+                    }
+                }
+            }
+
+            if show_hide {
+                self.visible = !self.visible;
+                frame.set_visible(self.visible);
+                tray.set_visible(self.visible);
+            }
+        }
+    }
+
+    /// Start the app again as the overlay or as the full window.
+    fn switch_overlay(&mut self, frame: &mut eframe::Frame) {
+        let mut overlay = self.app.data.read().storage.overlay;
+        overlay.enabled = !self.overlay;
+        self.relaunch(frame, overlay, false);
+    }
+
+    /// Save the `overlay` options and start the app again with them, click-through if `click_through` is true,
+    /// the window options cannot change once the window is open.
+    fn relaunch(&mut self, frame: &mut eframe::Frame, overlay: Overlay, click_through: bool) {
+        let mut data = self.app.data.write();
+        let previous = data.storage.overlay;
+        data.storage.overlay = overlay;
+        data.storage.save();
+
+        match Overlay::relaunch(click_through) {
+            Ok(()) => {
+                // The new process takes over, do not only hide to the tray.
+                self.quitting = true;
+                frame.close();
+            }
+            Err(e) => {
+                warn!("Cannot switch the overlay: {}", e);
+                data.storage.overlay = previous;
+                data.storage.save();
+            }
+        }
+    }
+
+    /// Render the Cetus cycle and the fissures of the active view that pass the filters,
+    /// on a see-through background sized to the content.
+    fn render_overlay(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+        let mut overlay = self.app.data.read().storage.overlay;
+        let mut click_through = false;
+        let (_, fissures, rows) = self.fissure_rows(self.active_view == ActiveView::VoidStorm);
+
+        let background = ctx.style().visuals.widgets.noninteractive.bg_fill;
+        let fill = Color32::from_rgba_unmultiplied(
+            background.r(),
+            background.g(),
+            background.b(),
+            (overlay.opacity * 255.0) as u8,
+        );
+
+        CentralPanel::default()
+            .frame(Frame::none().fill(fill).inner_margin(Margin::same(6.0)))
+            .show(ctx, |ui| {
+                ctx.request_repaint();
+
+                if !self.click_through {
+                    // Drag the window from anywhere, right click for the settings.
+                    let response = ui.interact(
+                        ui.max_rect(),
+                        ui.id().with("overlay_background"),
+                        Sense::click_and_drag(),
+                    );
+                    if response.drag_started() {
+                        frame.drag_window();
+                    }
+                    response.context_menu(|ui| {
+                        ui.add(Slider::new(&mut overlay.opacity, 0.2..=1.0).text(tr("Opacity")));
+                        if ui
+                            .button(tr("Click-through"))
+                            .on_hover_text(tr(
+                                "Leave it from the tray menu, or close the window and start Voidrat again",
+                            ))
+                            .clicked()
+                        {
+                            click_through = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(tr("Exit overlay")).clicked() {
+                            self.toggle_overlay = true;
+                            ui.close_menu();
+                        }
+                    });
+                }
+
+                ui.horizontal(|ui| self.render_cetus(ui));
+                ui.separator();
+
+                for fissure in &fissures {
+                    ui.horizontal(|ui| {
+                        self.tier_image(ctx, ui, &fissure.tier, 0.3);
+                        ui.label(i18n::mission(&fissure.mission));
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            let (fill, border) = fissure_badge_colors(fissure, &rows.countdown);
+                            ui.badge_frame(fill, border, |ui| {
+                                countdown_label(
+                                    ui,
+                                    duration_to_string(&fissure.till_expired()),
+                                    rows.countdown.progress_ring.then(|| fissure.progress()),
+                                );
+                            });
+                        });
+                    });
+                }
+
+                // Fit the window to the content.
+                let height = ui.min_rect().height() + 12.0;
+                if (ctx.input().screen_rect().height() - height).abs() > 1.0 {
+                    frame.set_window_size(Vec2::new(OVERLAY_WIDTH, height));
+                }
+            });
+
+        if click_through {
+            self.relaunch(frame, overlay, true);
+        } else if overlay != self.app.data.read().storage.overlay {
+            let mut data = self.app.data.write();
+            data.storage.overlay = overlay;
+            data.storage.save();
+        }
+    }
+
+    /// Returns the sorting and the sorted fissures or void storms that pass the filters and
+    /// the search, with the data the rows need.
+    fn fissure_rows(&self, show_storm: bool) -> (TableSorting, Vec<Fissure>, FissureRows) {
+        let (sorting, mut fissures, rows) = {
+            let data = self.app.data.read();
+            let sorting = if show_storm {
+                data.storage.storm_sorting
+            } else {
+                data.storage.fissure_sorting
+            };
+            let fissures = data
+                .fissures
+                .iter()
+                .filter(|f| {
+                    !f.has_expired()
+                        && show_storm == f.is_storm
+                        && data.storage.shows_fissure(f)
+                        && fissure_score(&self.search_query, f).is_some()
+                })
+                .cloned()
+                .collect::<Vec<Fissure>>();
+            let rows = FissureRows {
+                watched: data.storage.watched_fissures.clone(),
+                unseen: data.unseen.clone(),
+                relics: data.relics.clone(),
+                open_drops: None,
+                toggle_watched: None,
+                countdown: if show_storm {
+                    data.storage.countdowns.storms.clone()
+                } else {
+                    data.storage.countdowns.fissures.clone()
+                },
+            };
+
+            (sorting, fissures, rows)
+        };
+        sorting.sort_fissures(&mut fissures);
+
+        (sorting, fissures, rows)
+    }
+
+    /// Render the list of fissures or void storms, depending on `show_storm` boolean.
+    fn render_fissures(&mut self, ctx: &Context, ui: &mut eframe::egui::Ui, show_storm: bool) {
+        let (sorting, fissures, mut rows) = self.fissure_rows(show_storm);
+        let groups = group(fissures, |f| sorting.fissure_group(f));
+
+        // Column headers, clicking sorts by the column.
+        let mut new_sorting = sorting;
+        fissure_columns(TableBuilder::new(ui))
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    sort_header(ui, &mut new_sorting, SortKey::Tier, "Tier");
+                });
+                header.col(|ui| {
+                    ui.horizontal(|ui| {
+                        sort_header(ui, &mut new_sorting, SortKey::Mission, "Mission");
+                        sort_header(ui, &mut new_sorting, SortKey::Node, "Node");
+                        sort_header(ui, &mut new_sorting, SortKey::Planet, "Planet");
+                    });
+                });
+                header.col(|ui| {
+                    sort_header(ui, &mut new_sorting, SortKey::Time, "Time left");
+                });
+                header.col(|ui| {
+                    group_menu(
+                        ui,
+                        &mut new_sorting.group,
+                        &[GroupBy::Tier, GroupBy::Planet],
+                    );
+                });
+            })
+            .body(|_| {});
+
+        ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                ctx.request_repaint();
+
+                for (i, (name, fissures)) in groups.iter().enumerate() {
+                    ui.push_id(i, |ui| match name {
+                        Some(name) => {
+                            CollapsingHeader::new(format!("{} ({})", name, fissures.len()))
+                                .id_source(name)
+                                .default_open(true)
+                                .show(ui, |ui| self.fissure_table(ctx, ui, fissures, &mut rows));
+                        }
+                        None => self.fissure_table(ctx, ui, fissures, &mut rows),
+                    });
+                }
+            });
+
+        if new_sorting != sorting {
+            let mut data = self.app.data.write();
+            if show_storm {
+                data.storage.storm_sorting = new_sorting;
+            } else {
+                data.storage.fissure_sorting = new_sorting;
+            }
+            data.storage.save();
+        }
+
+        if let Some(key) = rows.toggle_watched {
+            self.app.data.write().storage.toggle_watched(key);
+        }
+
+        if let Some(tier) = rows.open_drops {
+            self.drops_tier = tier;
+            self.drops_query.clear();
+            self.show_drops = true;
+        }
+    }
+
+    /// Render the image of the fissure `tier`, scaled by the `size_modifier`.
+    fn tier_image(
+        &self,
+        ctx: &Context,
+        ui: &mut eframe::egui::Ui,
+        tier: &FissureTier,
+        size_modifier: f32,
+    ) -> Response {
+        let images = self.images.read();
+        let image = match tier {
+            FissureTier::Lith => &images.lith,
+            FissureTier::Meso => &images.meso,
+            FissureTier::Neo => &images.neo,
+            FissureTier::Axi => &images.axi,
+            FissureTier::Requiem => &images.requiem,
+            _ => return ui.label(tr("Unknown")),
+        };
+
+        ui.image(image.texture_id(ctx), image.size_vec2() * size_modifier)
+    }
+
+    /// Render a table with the `fissures`.
+    fn fissure_table(
+        &self,
+        ctx: &Context,
+        ui: &mut eframe::egui::Ui,
+        fissures: &[Fissure],
+        rows: &mut FissureRows,
+    ) {
+        fissure_columns(TableBuilder::new(ui)).body(|mut body| {
+            for fissure in fissures {
+                body.row(80.0, |mut row| {
+                    // Show fissure images.
+                    // 1st column.
+                    row.col(|ui| {
+                        self.tier_image(ctx, ui, &fissure.tier, 0.75);
+                    });
+
+                    // Basic fissure data.
+                    // 2nd column.
+                    row.col(|ui| {
+                        let text_color_override = if fissure.has_expired() {
+                            Some(Color32::GRAY)
+                        } else {
+                            None
+                        };
+
+                        // Override text color for expired fissures.
+                        ui.style_mut().visuals.override_text_color = text_color_override;
+
+                        ui.vertical(|ui| {
+                            ui.add_space(8.0);
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .add(
+                                        Label::new(
+                                            RichText::new(i18n::tier(&fissure.tier.to_string()))
+                                                .heading(),
+                                        )
+                                        .sense(Sense::click()),
+                                    )
+                                    .on_hover_text(format!(
+                                        "{}: {}",
+                                        tr("Relic drops"),
+                                        i18n::tier(&fissure.tier.to_string())
+                                    ))
+                                    .clicked()
+                                {
+                                    rows.open_drops = Some(fissure.tier.clone());
+                                }
+
+                                // Badge for owned relics of the same tier.
+                                let owned = rows.relics.count_for_tier(&fissure.tier);
+                                if owned > 0 {
+                                    let (fill, border) = theme::current().later.colors();
+                                    ui.badge_frame(fill, border, |ui| {
+                                        ui.colored_label(
+                                            theme::color(theme::current().badge_text),
+                                            format!("🎒 {}", owned),
+                                        );
+                                    })
+                                    .response
+                                    .on_hover_text(format!(
+                                        "{}: {} {}",
+                                        tr("Owned relics"),
+                                        owned,
+                                        i18n::tier(&fissure.tier.to_string())
+                                    ));
+                                }
+
+                                if rows.unseen.contains(&fissure.notification_key()) {
+                                    ui.new_badge();
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label(i18n::mission(&fissure.mission));
+                                if let Some(levels) = fissure.node.level_range() {
+                                    ui.weak(levels);
+                                }
+                            });
+                            ui.label(i18n::node(&fissure.node))
+                                .on_hover_text(node_details(&fissure.node));
+                        });
+                    });
+
+                    // Countdowns
+                    // 3rd column.
+                    row.col(|ui| {
+                        if fissure.has_expired() {
+                            let (fill, border) = theme::current().expired.colors();
+                            ui.grid_badge_frame(fill, border, |ui| {
+                                ui.colored_label(
+                                    theme::color(theme::current().expired_text),
+                                    RichText::new(tr("Expired")).text_style(TextStyle::Monospace),
+                                );
+                            });
+                        } else {
+                            // Figure out the correct badge background color.
+                            // For Void Capture missions only show violet.
+                            let (bg_color, border_color) =
+                                fissure_badge_colors(fissure, &rows.countdown);
+                            // Time left in human readable format
+                            let text = duration_to_string(&fissure.till_expired());
+
+                            ui.grid_badge_frame(bg_color, border_color, |ui| {
+                                countdown_label(
+                                    ui,
+                                    text,
+                                    rows.countdown.progress_ring.then(|| fissure.progress()),
+                                );
+                            });
+                        }
+                    });
+
+                    // 4th column.
+                    row.col(|ui| {
+                        let size_modifier = 0.5;
+                        ui.horizontal_centered(|ui| {
+                            if fissure.hard {
+                                ui.image(
+                                    self.images.read().steel_essence.texture_id(ctx),
+                                    self.images.read().steel_essence.size_vec2() * size_modifier,
+                                );
+                            }
+
+                            let key = fissure.notification_key();
+                            let mut watched = rows.watched.contains(&key);
+                            if ui
+                                .toggle_value(&mut watched, "👁")
+                                .on_hover_text(tr("Remind before this fissure expires"))
+                                .clicked()
+                            {
+                                rows.toggle_watched = Some(key);
+                            }
+                        });
+                    });
+                });
+            }
+        });
+    }
+
+    /// Render the top menu which has the buttons for displaying either fissures or void storms
+    /// and shows the current day/night cycle of Cetus.
+    fn render_top_menu(&mut self, ctx: &Context, ui: &mut eframe::egui::Ui) {
+        let previous_view = self.shown_view.clone();
+
+        ui.horizontal(|ui| {
+            let (fissures, storms, invasions) = {
+                let data = self.app.data.read();
+                (
+                    data.unseen_fissures(false),
+                    data.unseen_fissures(true),
+                    data.unseen_invasions(),
+                )
+            };
+
+            ui.toggled_button(
+                &mut self.active_view,
+                ActiveView::Fissure,
+                unread_text("Fissures", fissures),
+            );
+            ui.toggled_button(
+                &mut self.active_view,
+                ActiveView::VoidStorm,
+                unread_text("Void Storms", storms),
+            );
+            ui.toggled_button(
+                &mut self.active_view,
+                ActiveView::Invasion,
+                unread_text("Invasions", invasions),
+            );
+
+            if ui.button("🔔").clicked() {
+                self.show_notifications = !self.show_notifications;
+            }
+
+            if ui.button("📜").clicked() {
+                self.show_history = !self.show_history;
+            }
+
+            ui.add_space(10.0);
+
+            ctx.request_repaint();
+
+            self.render_cetus(ui);
+        });
+        self.render_search(ui);
+        if self.show_filters {
+            self.render_filters(ui);
+        }
+
+        // Everything in the view that was left has now been seen.
+        if self.active_view != previous_view {
+            self.shown_view = self.active_view.clone();
+            let mut data = self.app.data.write();
+            match previous_view {
+                ActiveView::Fissure => data.mark_fissures_seen(false),
+                ActiveView::VoidStorm => data.mark_fissures_seen(true),
+                ActiveView::Invasion => data.mark_invasions_seen(),
+            }
+        }
+    }
+
+    /// Render the search box and the best matches from every view.
+    /// Clicking a match switches to its view, the active view only shows what matches.
+    fn render_search(&mut self, ui: &mut eframe::egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            let response = ui.add(
+                TextEdit::singleline(&mut self.search_query)
+                    .hint_text(tr("Search nodes, missions, factions and rewards"))
+                    .desired_width(320.0),
+            );
+            if std::mem::take(&mut self.focus_search) {
+                response.request_focus();
+            }
+            if !self.search_query.is_empty() && ui.button("✖").clicked() {
+                self.search_query.clear();
+            }
+        });
+
+        if self.search_query.trim().is_empty() {
+            return;
+        }
+
+        let hits = {
+            let data = self.app.data.read();
+            search(
+                &self.search_query,
+                &data.filtered_fissures(),
+                &data.invasions,
+            )
+        };
+
+        if hits.is_empty() {
+            ui.label(tr("No matches"));
+        }
+
+        for (_, hit) in hits.iter().take(SEARCH_RESULTS) {
+            let (view, text) = match hit {
+                SearchHit::Fissure(fissure) if fissure.is_storm => (
+                    ActiveView::VoidStorm,
+                    format!(
+                        "{}: {} {}",
+                        tr("Void Storm"),
+                        i18n::tier(&fissure.tier.to_string()),
+                        i18n::node(&fissure.node)
+                    ),
+                ),
+                SearchHit::Fissure(fissure) => (
+                    ActiveView::Fissure,
+                    format!(
+                        "{}: {} {} {}",
+                        tr("Fissure"),
+                        i18n::tier(&fissure.tier.to_string()),
+                        i18n::mission(&fissure.mission),
+                        i18n::node(&fissure.node)
+                    ),
+                ),
+                SearchHit::Invasion(invasion) => (
+                    ActiveView::Invasion,
+                    format!(
+                        "{}: {} {}",
+                        tr("Invasion"),
+                        i18n::node(&invasion.node),
+                        invasion
+                            .rewards
+                            .attacker
+                            .iter()
+                            .chain(&invasion.rewards.defender)
+                            .map(i18n::reward)
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                ),
+            };
+
+            if ui
+                .selectable_label(self.active_view == view, text)
+                .clicked()
+            {
+                self.active_view = view;
+            }
+        }
+        if hits.len() > SEARCH_RESULTS {
+            ui.label(format!("{} {}", hits.len() - SEARCH_RESULTS, tr("more")));
+        }
+        ui.separator();
+    }
+
+    /// Render the fissure filters and presets.
+    /// Changes apply right away, saving writes them to the storage file.
+    fn render_filters(&mut self, ui: &mut eframe::egui::Ui) {
+        let (mut filter, presets, options) = {
+            let data = self.app.data.read();
+            let filter = data.storage.fissure_filter.clone();
+            let options = FilterOptions::new(&data.fissures, &filter);
+
+            (filter, data.storage.filter_presets.clone(), options)
+        };
+        let mut save_preset = false;
+        let mut delete_preset = None;
+
+        ui.separator();
+        ui.heading(tr("Fissure filters"));
+
+        filter_ui(ui, &mut filter, &options);
+
+        // Presets
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source("filter_presets")
+                .selected_text(tr("Presets"))
+                .show_ui(ui, |ui| {
+                    if presets.is_empty() {
+                        ui.label(tr("No presets saved."));
+                    }
+                    for (i, preset) in presets.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .selectable_label(preset.filter == filter, &preset.name)
+                                .clicked()
+                            {
+                                filter = preset.filter.clone();
+                            }
+                            if ui.small_button("✖").on_hover_text(tr("Delete")).clicked() {
+                                delete_preset = Some(i);
+                            }
+                        });
+                    }
+                });
+
+            ui.add(
+                TextEdit::singleline(&mut self.filter_preset_name)
+                    .hint_text(tr("Preset name"))
+                    .desired_width(120.0),
+            );
+            if ui
+                .add_enabled(
+                    !self.filter_preset_name.trim().is_empty(),
+                    Button::new(tr("Save preset")),
+                )
+                .clicked()
+            {
+                save_preset = true;
+            }
+
+            ui.with_layout(
+                Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                |ui| {
+                    if ui.button(tr("Save")).clicked() {
+                        // Hide filters.
+                        self.show_filters = false;
+                        // Save to file.
+                        self.app.data.write().storage.save();
+                    }
+                },
+            );
+        });
+
+        let mut data = self.app.data.write();
+        data.storage.fissure_filter = filter;
+
+        if let Some(i) = delete_preset {
+            data.storage.filter_presets.remove(i);
+            data.storage.save();
+        }
+        if save_preset {
+            data.storage.save_filter_preset(&self.filter_preset_name);
+            self.filter_preset_name.clear();
+        }
+    }
+
+    fn dnd_menu_button(&mut self, ui: &mut eframe::egui::Ui) {
+        let dnd_until = self.app.data.read().storage.dnd_until;
+        let dnd_active = self.app.data.read().storage.dnd_active();
+
+        let text = match dnd_until {
+            Some(until) if dnd_active && until != i64::MAX => format!(
+                "🔕 {}",
+                duration_to_string(&Duration::seconds(until - clock::now().timestamp()))
+            ),
+            _ if dnd_active => "🔕 DND".to_string(),
+            _ => "🔔 DND".to_string(),
+        };
+
+        ui.menu_button(text, |ui| {
+            for (label, minutes) in DND_DURATIONS {
+                if ui.button(tr(label)).clicked() {
+                    let until = minutes.map_or(i64::MAX, |m| clock::now().timestamp() + m * 60);
+                    self.app.data.write().storage.dnd_until = Some(until);
+                    self.app.data.write().storage.save();
+                    ui.close_menu();
+                }
+            }
+
+            ui.separator();
+
+            if ui
+                .add_enabled(dnd_active, Button::new(tr("Turn off")))
+                .clicked()
+            {
+                self.app.data.write().storage.dnd_until = None;
+                self.app.data.write().storage.save();
+                ui.close_menu();
+            }
+        });
+    }
+
+    /// Render the app version and the data pack with a way to update it.
+    fn render_about_window(&mut self, ctx: &Context) {
+        Window::new(tr("About"))
+            .default_width(360.0)
+            .min_width(360.0)
+            .default_pos(Pos2::new(24.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let pack = DataPack::current();
+
+                ui.heading(format!("Voidrat {}", env!("CARGO_PKG_VERSION")));
+                ui.add_space(8.0);
+
+                if pack.is_empty() {
+                    ui.label(tr("Data pack: none, using the bundled data"));
+                } else {
+                    ui.label(format!("{}: {}", tr("Data pack"), pack.version));
+                    if !pack.source.is_empty() {
+                        ui.label(format!("{}: {}", tr("Source"), pack.source));
+                    }
+                    ui.label(format!(
+                        "{}: {}, {}: {}, {}: {}, {}: {}",
+                        tr("Nodes"),
+                        pack.nodes.len(),
+                        tr("Items"),
+                        pack.items.len(),
+                        tr("Mission types"),
+                        pack.mission_types.len(),
+                        tr("Challenges"),
+                        pack.challenges.len()
+                    ));
+                }
+                ui.label(format!(
+                    "{}: {}",
+                    tr("Solar nodes known"),
+                    SolarNodes::shared().len()
+                ));
+
+                ui.add_space(8.0);
+                ui.label(tr("Data pack url"));
+                ui.add(
+                    TextEdit::singleline(&mut self.data_pack_url)
+                        .hint_text("https://.../data_pack.json")
+                        .desired_width(f32::INFINITY),
+                );
+                if let Some(status) = self.data_pack_status.read().as_ref() {
+                    ui.label(status);
+                }
+
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_about = false;
+                        }
+                        if ui
+                            .add_enabled(
+                                !self.data_pack_url.trim().is_empty(),
+                                Button::new(tr("⬇ Update")),
+                            )
+                            .clicked()
+                        {
+                            let url = self.data_pack_url.trim().to_string();
+                            let timeout = {
+                                let mut data = self.app.data.write();
+                                data.storage.data_pack_url = url.clone();
+                                data.storage.save();
+                                data.storage.request_timeout
+                            };
+                            *self.data_pack_status.write() = Some(tr("Updating..."));
+
+                            let status = self.data_pack_status.clone();
+                            thread::spawn(move || {
+                                *status.write() = Some(match DataPack::update(&url, timeout) {
+                                    Ok(pack) => format!(
+                                        "{}: {}. {}",
+                                        tr("Updated to"),
+                                        pack.version,
+                                        tr("Used from the next data update.")
+                                    ),
+                                    Err(e) => format!("{}: {}", tr("Update failed"), e),
+                                });
+                            });
+                        }
+                    },
+                );
+            });
+    }
+
+    fn file_menu_button(&mut self, ui: &mut eframe::egui::Ui) {
+        ui.menu_button(tr("File"), |ui| {
+            ui.menu_button(tr("Export"), |ui| {
+                for format in ExportFormat::ALL {
+                    if ui.button(format.to_string()).clicked() {
+                        self.export_status = Some(match export(&self.app.data.read(), format) {
+                            Ok(files) => format!(
+                                "{}: {}",
+                                tr("Exported"),
+                                files
+                                    .iter()
+                                    .map(|f| f.display().to_string())
+                                    .collect::<Vec<String>>()
+                                    .join(", ")
+                            ),
+                            Err(e) => format!("{}: {}", tr("Export failed"), e),
+                        });
+                    }
+                }
+
+                if let Some(status) = &self.export_status {
+                    ui.separator();
+                    ui.label(status);
+                }
+            });
+            let hint = self.keybindings().hint(Action::OpenSettings);
+            if ui.button(format!("{}{}", tr("Settings"), hint)).clicked() {
+                self.open_settings();
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.button(tr("Exit")).clicked() {
+                process::exit(0);
+            }
+        });
+        ui.menu_button(tr("History"), |ui| {
+            if ui.button(tr("Notifications")).clicked() {
+                self.show_history = true;
+                ui.close_menu();
+            }
+            if ui.button(tr("Fissures & invasions")).clicked() {
+                self.search_archive();
+                self.show_archive = true;
+                ui.close_menu();
+            }
+            if ui.button(tr("Statistics")).clicked() {
+                self.collect_stats();
+                self.show_stats = true;
+                ui.close_menu();
+            }
+        });
+        ui.menu_button(tr("Relics"), |ui| {
+            if ui.button(tr("Inventory")).clicked() {
+                // Start editing from the saved inventory.
+                self.relic_inventory = self.app.data.read().relics.clone();
+                self.relic_import_status = None;
+                self.show_relics = true;
+                ui.close_menu();
+            }
+            if ui.button(tr("Drop tables")).clicked() {
+                self.show_drops = true;
+                ui.close_menu();
+            }
+        });
+        ui.menu_button(tr("Filter"), |ui| {
+            if ui
+                .checkbox(&mut self.show_filters, tr("Toggle filters"))
+                .changed()
+            {
+                ui.close_menu();
+            }
+        });
+        ui.menu_button(tr("View"), |ui| {
+            let current = self.app.data.read().storage.theme;
+            for kind in ThemeKind::ALL {
+                if ui
+                    .selectable_label(current == kind, tr(&kind.to_string()))
+                    .clicked()
+                {
+                    self.set_theme(ui.ctx(), kind);
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
+            if ui.button(tr("Edit custom theme")).clicked() {
+                self.custom_theme = self.app.data.read().storage.custom_theme;
+                self.show_theme_editor = true;
+                ui.close_menu();
+            }
+            ui.separator();
+            let mut storage_changed = false;
+            ui.add_enabled_ui(self.tray.is_some(), |ui| {
+                storage_changed |= ui
+                    .checkbox(
+                        &mut self.app.data.write().storage.minimize_to_tray,
+                        tr("Minimize to tray on close"),
+                    )
+                    .changed();
+                storage_changed |= ui
+                    .checkbox(
+                        &mut self.app.data.write().storage.start_minimized,
+                        tr("Start minimized to tray"),
+                    )
+                    .changed();
+            })
+            .response
+            .on_disabled_hover_text(no_tray_text());
+            if storage_changed {
+                self.app.data.write().storage.save();
+            }
+            ui.separator();
+            let hint = self.keybindings().hint(Action::ToggleOverlay);
+            if ui.button(format!("{}{}", tr("Overlay"), hint)).clicked() {
+                self.toggle_overlay = true;
+                ui.close_menu();
+            }
+            if ui.button(tr("Countdown colors")).clicked() {
+                self.countdowns = self.app.data.read().storage.countdowns.clone();
+                self.show_countdowns = true;
+                ui.close_menu();
+            }
+        });
+        ui.menu_button(tr("Language"), |ui| {
+            let current = i18n::language();
+            for language in Language::ALL {
+                if ui
+                    .selectable_label(current == language, language.to_string())
+                    .clicked()
+                {
+                    i18n::set_language(language);
+                    let mut data = self.app.data.write();
+                    data.storage.language = language;
+                    data.storage.save();
+                    ui.close_menu();
+                }
+            }
+        });
+        ui.menu_button(tr("Help"), |ui| {
+            let bindings = self.keybindings();
+            for action in [Action::CommandPalette, Action::ShowShortcuts] {
+                let text = format!("{}{}", tr(&action.to_string()), bindings.hint(action));
+                if ui.button(text).clicked() {
+                    self.run_action(action);
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
+            if ui.button(tr("About")).clicked() {
+                self.show_about = true;
+                ui.close_menu();
+            }
+        });
+    }
+}
+
+impl eframe::App for UI {
+    fn clear_color(&self, visuals: &Visuals) -> Rgba {
+        if self.overlay {
+            return Rgba::TRANSPARENT;
+        }
+
+        visuals.window_fill().into()
+    }
+
+    /// Hide to the tray instead when it is enabled.
+    fn on_close_event(&mut self) -> bool {
+        if self.quitting || self.tray.is_none() || !self.app.data.read().storage.minimize_to_tray {
+            return true;
+        }
+
+        self.hide = true;
+        false
+    }
+
+    /// The overlay size and position would replace the ones of the full window.
+    fn persist_native_window(&self) -> bool {
+        !self.overlay
+    }
+
+    fn update(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
+        self.handle_tray(frame);
+
+        self.handle_keys(ctx);
+        if self.toggle_overlay {
+            self.toggle_overlay = false;
+            self.switch_overlay(frame);
+        }
+
+        if self.overlay {
+            if self.app.data.read().initialized {
+                self.render_overlay(ctx, frame);
+            }
+            return;
+        }
+
+        if self.show_notifications {
+            self.render_notification_window(ctx);
+        }
+
+        if self.show_history {
+            self.render_history_window(ctx);
+        }
+
+        if self.show_relics {
+            self.render_relics_window(ctx);
+        }
+
+        if self.show_drops {
+            self.render_drops_window(ctx);
+        }
+
+        if self.show_archive {
+            self.render_archive_window(ctx);
+        }
+
+        if self.show_stats {
+            self.render_stats_window(ctx);
+        }
+
+        if self.show_about {
+            self.render_about_window(ctx);
+        }
+
+        if self.show_theme_editor {
+            self.render_theme_window(ctx);
+        }
+
+        if self.show_countdowns {
+            self.render_countdowns_window(ctx);
+        }
+
+        if self.show_settings {
+            self.render_settings_window(ctx);
+        }
+
+        if self.show_palette {
+            self.render_palette(ctx);
+        }
+
+        if self.show_shortcuts {
+            self.render_shortcuts_window(ctx);
+        }
+
+        self.render_toasts(ctx);
+        self.render_replay_bar(ctx);
+
+        // Not sure if this is less taxing down the line..
+        if !self.initialized && self.app.data.read().initialized {
+            self.initialized = true;
+        }
+
+        // instead of using `self.app.data.read()..` every update, forever.
+        if self.initialized {
+            TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+                menu::bar(ui, |ui| {
+                    self.file_menu_button(ui);
+                    self.dnd_menu_button(ui);
+                });
+            });
+            self.render_status_bar(ctx);
+
+            CentralPanel::default().show(ctx, |ui| {
+                ui.add_space(8.0);
+
+                self.render_top_menu(ctx, ui);
+
+                Separator::default().spacing(24.0).horizontal().ui(ui);
+
+                match self.active_view {
+                    ActiveView::Fissure => self.render_fissures(ctx, ui, false),
+                    ActiveView::VoidStorm => self.render_fissures(ctx, ui, true),
+                    ActiveView::Invasion => self.render_invasions(ctx, ui),
+                }
+            });
+        } else {
+            // Loading text which should be visible for very short duration
+            // during app startup.
+            CentralPanel::default().show(ctx, |ui| {
+                ui.horizontal_centered(|ui| {
+                    ui.add_space(180.0);
+
+                    ctx.request_repaint();
+
+                    let loading = RichText::from(self.loading_text.animate()).size(32.0);
+
+                    ui.heading(loading);
+                });
+            });
+        }
+    }
+}
+
+/// Checkbox for each of the `values`, unchecked ones are `hidden`.
+/// The `values` are shown translated with `name`.
+fn filter_grid(
+    ui: &mut eframe::egui::Ui,
+    id: &str,
+    values: &[String],
+    hidden: &mut Vec<String>,
+    name: fn(&str) -> String,
+) {
+    Grid::new(id).show(ui, |ui| {
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 && i % 4 == 0 {
+                ui.end_row();
+            }
+
+            let mut shown = !hidden.contains(value);
+            if ui.checkbox(&mut shown, name(value)).changed() {
+                set_shown(hidden, value, shown);
+            }
+        }
+    });
+}
+
+/// Combo box for a filter that is either off, shows only or hides.
+fn only_or_hide_combo(ui: &mut eframe::egui::Ui, id: &str, value: &mut Option<bool>) {
+    let text = match value {
+        None => tr("Any"),
+        Some(true) => tr("Only"),
+        Some(false) => tr("Hide"),
+    };
+
+    ComboBox::from_id_source(id)
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(value, None, tr("Any"));
+            ui.selectable_value(value, Some(true), tr("Only"));
+            ui.selectable_value(value, Some(false), tr("Hide"));
+        });
+}
+
+/// Returns the badge fill and border of the fissure countdown, violet for Void Capture.
+fn fissure_badge_colors(fissure: &Fissure, countdown: &Thresholds) -> (Color32, Color32) {
+    if fissure.is_void_capture() {
+        theme::current().void_capture.colors()
+    } else {
+        countdown.colors(&fissure.till_expired()).colors()
+    }
+}
+
+/// Render the countdown `text` of a badge, after a ring when there is a `progress`.
+fn countdown_label(ui: &mut eframe::egui::Ui, text: String, progress: Option<f32>) {
+    ui.horizontal(|ui| {
+        let text_color = theme::color(theme::current().badge_text);
+        if let Some(progress) = progress {
+            ui.progress_ring(progress, text_color);
+        }
+        ui.colored_label(
+            text_color,
+            RichText::new(text).text_style(TextStyle::Monospace),
+        );
+    });
+}
+
+/// Render the missions, tiers, planets, factions and variants the `filter` hides.
+fn filter_ui(ui: &mut eframe::egui::Ui, filter: &mut FissureFilter, options: &FilterOptions) {
+    CollapsingHeader::new(tr("Missions"))
+        .default_open(true)
+        .show(ui, |ui| {
+            filter_grid(
+                ui,
+                "filter_missions",
+                &options.missions,
+                &mut filter.hidden_missions,
+                i18n::mission,
+            );
+        });
+    CollapsingHeader::new(tr("Tiers")).show(ui, |ui| {
+        filter_grid(
+            ui,
+            "filter_tiers",
+            &options.tiers,
+            &mut filter.hidden_tiers,
+            i18n::tier,
+        );
+    });
+    CollapsingHeader::new(tr("Planets")).show(ui, |ui| {
+        filter_grid(
+            ui,
+            "filter_planets",
+            &options.planets,
+            &mut filter.hidden_planets,
+            i18n::planet,
+        );
+    });
+    CollapsingHeader::new(tr("Factions")).show(ui, |ui| {
+        filter_grid(
+            ui,
+            "filter_factions",
+            &options.factions,
+            &mut filter.hidden_factions,
+            i18n::faction,
+        );
+    });
+
+    Grid::new("filter_variant_grid").show(ui, |ui| {
+        ui.label(tr("Steel Path"));
+        only_or_hide_combo(ui, "filter_steel_path", &mut filter.steel_path);
+        ui.end_row();
+
+        ui.label(tr("Void Storms"));
+        only_or_hide_combo(ui, "filter_storms", &mut filter.storms);
+        ui.end_row();
+    });
+}
+
+/// Fissure table columns: tier, mission, time left and actions.
+fn fissure_columns(table: TableBuilder) -> TableBuilder {
+    table
+        .column(Column::exact(90.0))
+        .column(Column::exact(160.0))
+        .column(Column::exact(124.0))
+        .column(Column::exact(96.0))
+}
+
+/// Invasion table columns: defender rewards, separator, attacker rewards and node.
+fn invasion_columns(table: TableBuilder) -> TableBuilder {
+    table
+        .column(Column::exact(120.0))
+        .column(Column::exact(30.0))
+        .column(Column::exact(120.0))
+        .column(Column::exact(200.0))
+}
+
+/// Clickable column header that sorts the table by the `key`.
+fn sort_header(ui: &mut eframe::egui::Ui, sorting: &mut TableSorting, key: SortKey, text: &str) {
+    let text = RichText::new(format!("{}{}", tr(text), sorting.arrow(key))).strong();
+
+    if ui.add(Label::new(text).sense(Sense::click())).clicked() {
+        sorting.toggle(key);
+    }
+}
+
+/// Menu to pick how the table rows are grouped.
+fn group_menu(ui: &mut eframe::egui::Ui, group: &mut GroupBy, options: &[GroupBy]) {
+    ui.menu_button(tr(&group.to_string()), |ui| {
+        for option in [GroupBy::None].iter().chain(options) {
+            if ui
+                .selectable_label(group == option, tr(&option.to_string()))
+                .clicked()
+            {
+                *group = *option;
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+/// Hover text of the tray options when there is no tray icon.
+fn no_tray_text() -> String {
+    if cfg!(target_os = "linux") {
+        tr("No system tray found")
+    } else {
+        tr("The tray icon is only available on Linux")
+    }
+}
+
+/// Show the error `text` on a badge with the urgent colors of the theme.
+fn error_badge(ui: &mut eframe::egui::Ui, text: impl Into<RichText>) {
+    let theme = theme::current();
+    let (fill, border) = theme.urgent.colors();
+
+    ui.badge_frame(fill, border, |ui| {
+        ui.colored_label(theme::color(theme.badge_text), text);
+    });
+}
+
+/// Returns the faction, level range, tileset and mission flags of the `node`, one per line.
+fn node_details(node: &SolarNode) -> String {
+    let mut lines = vec![];
+
+    if let Some(enemy) = &node.enemy {
+        lines.push(format!("{}: {}", tr("Faction"), enemy));
+    }
+    if let Some(levels) = node.level_range() {
+        lines.push(format!("{}: {}", tr("Enemy level"), levels));
+    }
+    if let Some(tileset) = &node.tileset {
+        lines.push(format!("{}: {}", tr("Tileset"), tileset));
+    }
+    for (flag, text) in [
+        (node.dark_sector, "Dark Sector"),
+        (node.archwing, "Archwing"),
+        (node.railjack, "Railjack"),
+    ] {
+        if flag {
+            lines.push(tr(text));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Returns the tab `text` with the unread `count`, e.g: `Fissures (3)`.
+fn unread_text(text: &str, count: usize) -> String {
+    if count > 0 {
+        format!("{} ({})", tr(text), count)
+    } else {
+        tr(text)
+    }
+}
+
+/// Custom styles for the UI with the colors of the `theme`.
+/// The `theme` is also used for the badges from now on.
+fn ui_style(ctx: &Context, theme: Theme) {
+    theme::set(theme);
+    let mut style = (*ctx.style()).clone();
+
+    style.visuals = if theme.dark {
+        Visuals::dark()
+    } else {
+        Visuals::light()
+    };
+
+    let base = WidgetVisuals {
+        bg_fill: theme::color(theme.background),
+        bg_stroke: Stroke {
+            width: 1.0,
+            color: theme::color(theme.border), // Separator, border color
+        },
+        rounding: Rounding::none(),
+        expansion: 0.0,
+        fg_stroke: Stroke {
+            width: 1.0,
+            color: theme::color(theme.text), // Text color
+        },
+    };
+
+    style.visuals.widgets.noninteractive = WidgetVisuals { ..base };
+
+    // Styles that the toggle button uses.
+    style.visuals.selection.bg_fill = theme::color(theme.selection);
+    style.visuals.selection.stroke = Stroke {
+        width: 1.0,
+        color: theme::color(theme.selection_border),
+    };
+
+    // Button etc. not hovered
+    style.visuals.widgets.inactive = WidgetVisuals {
+        bg_fill: theme::color(theme.button),
+        bg_stroke: Stroke {
+            width: 1.0,
+            color: theme::color(theme.button_border),
+        },
+        ..base
+    };
+
+    // Button etc. hover
+    style.visuals.widgets.hovered = WidgetVisuals {
+        bg_fill: theme::color(theme.button),
+        bg_stroke: Stroke {
+            width: 1.0,
+            color: theme::color(theme.border),
+        },
+        ..base
+    };
+
+    style.visuals.widgets.active = WidgetVisuals {
+        bg_fill: theme::color(theme.selection),
+        ..base
+    };
+
+    // Scrollbar bg color.
+    style.visuals.extreme_bg_color = theme::color(theme.extreme_background);
+
+    // Padding for the buttons.
+    style.spacing.button_padding = Vec2::new(12.0, 8.0);
+
+    // Monospace = badge text
+    style
+        .text_styles
+        .get_mut(&TextStyle::Monospace)
+        .unwrap()
+        .size = 16.0;
+
+    // style.text_styles.get_mut(&TextStyle::Body).unwrap().size = 16.0;
+
+    // Save the new styles.
+    ctx.set_style(style);
+}
@@ -0,0 +1,228 @@
+use super::UI;
+use crate::clock;
+use crate::filters::{FilterOptions, FissureFilter};
+use crate::i18n::{self, tr};
+use crate::notifications::{Delivery, NotificationSettings, QuietHours};
+use crate::theme::{self};
+use crate::widgets::UiExt;
+
+use eframe::egui::{
+    Align, Align2, Area, ComboBox, Context, Direction, DragValue, Grid, Layout, Pos2, TextStyle,
+    Vec2, Window,
+};
+
+use crate::voidrat::play_notification_sound;
+use std::thread;
+
+/// Render the audio notifications, reminders and quiet hours of the `settings`.
+/// `missions` are the mission types that can be liked.
+pub(super) fn notification_settings_ui(
+    ui: &mut eframe::egui::Ui,
+    settings: &mut NotificationSettings,
+    missions: &[String],
+) {
+    // Audio notification
+    ui.heading(tr("Play audio notification"));
+    ui.add_space(8.0);
+    ui.style_mut()
+        .text_styles
+        .get_mut(&TextStyle::Button)
+        .unwrap()
+        .size = 16.0;
+    ui.checkbox(
+        &mut settings.fissure_void_capture,
+        tr("Fissure Void Capture spotted"),
+    );
+    ui.checkbox(
+        &mut settings.invasion_epic,
+        tr("Invasion epic reward (Forma / Orokin x) spotted"),
+    );
+    ui.checkbox(
+        &mut settings.fissure_owned_relic,
+        tr("Fissure for owned relics with a liked mission type"),
+    );
+    if settings.fissure_owned_relic {
+        Grid::new("liked_missions_grid").show(ui, |ui| {
+            for (i, mission) in missions.iter().enumerate() {
+                if i > 0 && i % 4 == 0 {
+                    ui.end_row();
+                }
+
+                let liked = &mut settings.liked_missions;
+                let mut selected = liked.iter().any(|m| m == mission);
+                if ui
+                    .toggle_value(&mut selected, i18n::mission(mission))
+                    .clicked()
+                {
+                    if selected {
+                        liked.push(mission.to_string());
+                    } else {
+                        liked.retain(|m| m != mission);
+                    }
+                }
+            }
+        });
+    }
+    ui.add_space(8.0);
+    if ui.button(tr("▶ Test")).clicked() {
+        thread::spawn(play_notification_sound);
+    }
+    // Reminders
+    ui.add_space(8.0);
+    ui.heading(tr("Reminders"));
+    ui.add_space(8.0);
+    Grid::new("reminder_grid").show(ui, |ui| {
+        for (reminder, text) in [
+            (&mut settings.reminders.cetus_night, "Cetus night starts"),
+            (&mut settings.reminders.cetus_day, "Cetus day starts"),
+            (
+                &mut settings.reminders.fissure_expiry,
+                "Watched fissure expires",
+            ),
+        ] {
+            ui.checkbox(&mut reminder.enabled, tr(text));
+            ui.add_enabled(
+                reminder.enabled,
+                DragValue::new(&mut reminder.lead_minutes)
+                    .clamp_range(1..=60)
+                    .suffix(format!(" {}", tr("min before"))),
+            );
+            ui.end_row();
+        }
+    });
+    // Quiet hours
+    ui.add_space(8.0);
+    ui.heading(tr("Quiet hours"));
+    ui.label(tr(
+        "Suppressed notifications are still kept in the history.",
+    ));
+    ui.add_space(8.0);
+    let mut remove = None;
+    for (i, quiet) in settings.quiet_hours.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            for (day, name) in WEEKDAYS.iter().enumerate() {
+                ui.toggle_value(&mut quiet.days[day], tr(name));
+            }
+        });
+        ui.horizontal(|ui| {
+            time_drag_value(ui, &mut quiet.start);
+            ui.label("-");
+            time_drag_value(ui, &mut quiet.end);
+
+            ComboBox::from_id_source(("quiet_hours_delivery", i))
+                .selected_text(tr(&quiet.delivery.to_string()))
+                .show_ui(ui, |ui| {
+                    for delivery in [Delivery::Visual, Delivery::Suppressed] {
+                        ui.selectable_value(
+                            &mut quiet.delivery,
+                            delivery,
+                            tr(&delivery.to_string()),
+                        );
+                    }
+                });
+
+            if ui.button("✖").clicked() {
+                remove = Some(i);
+            }
+        });
+        ui.add_space(4.0);
+    }
+    if let Some(i) = remove {
+        settings.quiet_hours.remove(i);
+    }
+    if ui.button(tr("Add quiet hours")).clicked() {
+        settings.quiet_hours.push(QuietHours::default());
+    }
+}
+
+/// Edit minutes after midnight as hours and minutes.
+fn time_drag_value(ui: &mut eframe::egui::Ui, minutes: &mut u32) {
+    let mut hours = *minutes / 60;
+    let mut mins = *minutes % 60;
+
+    ui.add(
+        DragValue::new(&mut hours)
+            .clamp_range(0..=23)
+            .custom_formatter(|n, _| format!("{:0>2}", n)),
+    );
+    ui.label(":");
+    ui.add(
+        DragValue::new(&mut mins)
+            .clamp_range(0..=59)
+            .custom_formatter(|n, _| format!("{:0>2}", n)),
+    );
+
+    *minutes = hours * 60 + mins;
+}
+
+pub(super) const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// How many seconds a delivered notification is shown in the corner.
+pub(super) const TOAST_DURATION: i64 = 10;
+
+impl UI {
+    pub(super) fn render_notification_window(&mut self, ctx: &Context) {
+        Window::new(tr("Notification"))
+            .default_width(330.0)
+            .min_width(330.0)
+            .fixed_pos(Pos2::new(60.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let missions =
+                    FilterOptions::new(&self.app.data.read().fissures, &FissureFilter::default())
+                        .missions;
+                notification_settings_ui(ui, &mut self.notification_settings, &missions);
+                ui.add_space(8.0);
+                // Buttons
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_notifications = false;
+                        }
+                        if ui.button(tr("Save")).clicked() {
+                            self.app
+                                .data
+                                .write()
+                                .storage
+                                .save_notification(self.notification_settings.clone());
+                            self.show_notifications = false;
+                        }
+                    },
+                )
+            });
+    }
+
+    /// Render the delivered notifications in the bottom right corner for a short while.
+    pub(super) fn render_toasts(&mut self, ctx: &Context) {
+        let now = clock::now().timestamp();
+
+        self.app
+            .data
+            .write()
+            .toasts
+            .retain(|n| n.fired_at + TOAST_DURATION > now);
+
+        let toasts = self.app.data.read().toasts.clone();
+        if toasts.is_empty() {
+            return;
+        }
+
+        ctx.request_repaint();
+
+        Area::new("notification_toasts")
+            .anchor(Align2::RIGHT_BOTTOM, Vec2::new(-8.0, -8.0))
+            .show(ctx, |ui| {
+                for n in toasts.iter().rev() {
+                    let (fill, border) = theme::current().void_capture.colors();
+                    let text = theme::color(theme::current().badge_text);
+                    ui.badge_frame(fill, border, |ui| {
+                        ui.colored_label(text, format!("🔔 {}", tr(&n.rule.to_string())));
+                        ui.colored_label(text, n.key.to_string());
+                    });
+                    ui.add_space(4.0);
+                }
+            });
+    }
+}
@@ -0,0 +1,86 @@
+use super::UI;
+use crate::i18n::tr;
+use crate::keybindings::Action;
+use crate::search::fuzzy_score;
+use std::cmp::Reverse;
+
+use eframe::egui::{Align, Align2, Context, Key, Layout, TextEdit, Vec2, Window};
+
+impl UI {
+    /// Render the actions that match the typed text, Enter or a click runs the highlighted one.
+    pub(super) fn render_palette(&mut self, ctx: &Context) {
+        let bindings = self.keybindings();
+        let mut run = None;
+
+        {
+            let input = ctx.input();
+            if input.key_pressed(Key::Escape) {
+                self.show_palette = false;
+                return;
+            }
+            if input.key_pressed(Key::ArrowDown) {
+                self.palette_selected += 1;
+            }
+            if input.key_pressed(Key::ArrowUp) {
+                self.palette_selected = self.palette_selected.saturating_sub(1);
+            }
+        }
+
+        Window::new(tr("Command palette"))
+            .anchor(Align2::CENTER_TOP, Vec2::new(0.0, 60.0))
+            .fixed_size(Vec2::new(320.0, 0.0))
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.palette_query)
+                        .hint_text(tr("Type a command"))
+                        .desired_width(f32::INFINITY),
+                )
+                .request_focus();
+                ui.separator();
+
+                let mut actions = Action::ALL
+                    .into_iter()
+                    .filter(|a| *a != Action::CommandPalette)
+                    .filter_map(|a| {
+                        Some((fuzzy_score(&self.palette_query, &tr(&a.to_string()))?, a))
+                    })
+                    .collect::<Vec<(u32, Action)>>();
+                actions.sort_by_key(|(score, _)| Reverse(*score));
+
+                if actions.is_empty() {
+                    ui.label(tr("No matches"));
+                    return;
+                }
+                self.palette_selected = self.palette_selected.min(actions.len() - 1);
+
+                for (i, (_, action)) in actions.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let selected = i == self.palette_selected;
+                        if ui
+                            .selectable_label(selected, tr(&action.to_string()))
+                            .clicked()
+                        {
+                            run = Some(*action);
+                        }
+                        if let Some(binding) = bindings.get(*action) {
+                            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                                ui.weak(binding.to_string());
+                            });
+                        }
+                    });
+                }
+
+                if ctx.input().key_pressed(Key::Enter) {
+                    run = Some(actions[self.palette_selected].1);
+                }
+            });
+
+        if let Some(action) = run {
+            self.show_palette = false;
+            self.run_action(action);
+        }
+    }
+}
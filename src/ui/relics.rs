@@ -0,0 +1,124 @@
+use super::UI;
+use crate::i18n::{self, tr};
+use crate::parsers::FissureTier;
+use crate::relics::{Refinement, Relic, RelicInventory};
+
+use eframe::egui::{
+    Align, ComboBox, Context, Direction, DragValue, Grid, Layout, Pos2, ScrollArea, TextEdit,
+    Window,
+};
+
+use log::warn;
+use std::path::PathBuf;
+
+impl UI {
+    pub(super) fn render_relics_window(&mut self, ctx: &Context) {
+        Window::new(tr("Relic inventory"))
+            .default_width(420.0)
+            .min_width(420.0)
+            .default_pos(Pos2::new(40.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if self.relic_inventory.relics.is_empty() {
+                    ui.label(tr("No relics yet."));
+                }
+
+                let mut remove = None;
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    Grid::new("relic_inventory_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (i, relic) in self.relic_inventory.relics.iter_mut().enumerate() {
+                                ComboBox::from_id_source(("relic_tier", i))
+                                    .width(80.0)
+                                    .selected_text(i18n::tier(&relic.tier.to_string()))
+                                    .show_ui(ui, |ui| {
+                                        for tier in FissureTier::ALL {
+                                            let text = i18n::tier(&tier.to_string());
+                                            ui.selectable_value(&mut relic.tier, tier, text);
+                                        }
+                                    });
+                                ui.add(TextEdit::singleline(&mut relic.name).desired_width(60.0));
+                                ui.add(DragValue::new(&mut relic.count).prefix("x"));
+                                ComboBox::from_id_source(("relic_refinement", i))
+                                    .width(100.0)
+                                    .selected_text(tr(&relic.refinement.to_string()))
+                                    .show_ui(ui, |ui| {
+                                        for refinement in Refinement::ALL {
+                                            ui.selectable_value(
+                                                &mut relic.refinement,
+                                                refinement,
+                                                tr(&refinement.to_string()),
+                                            );
+                                        }
+                                    });
+                                if ui.button("✖").clicked() {
+                                    remove = Some(i);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+                if let Some(i) = remove {
+                    self.relic_inventory.relics.remove(i);
+                }
+
+                if ui.button(tr("Add relic")).clicked() {
+                    self.relic_inventory.relics.push(Relic::default());
+                }
+
+                // Import
+                ui.add_space(8.0);
+                ui.heading(tr("Import"));
+                ui.label(tr(
+                    "CSV lines as tier,name,count,refinement or a JSON list.",
+                ));
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.relic_import_path)
+                            .hint_text(tr("Path to .csv or .json file")),
+                    );
+                    if ui.button(tr("Import")).clicked() {
+                        let path = PathBuf::from(self.relic_import_path.trim());
+
+                        self.relic_import_status = Some(match RelicInventory::import(&path) {
+                            Ok(relics) => {
+                                let count = relics.len();
+                                self.relic_inventory.merge(relics);
+                                format!("{}: {}", tr("Imported relics"), count)
+                            }
+                            Err(e) => format!("{}: {}", tr("Import failed"), e),
+                        });
+                    }
+                });
+                if let Some(status) = &self.relic_import_status {
+                    ui.label(status);
+                }
+
+                // Buttons
+                ui.add_space(8.0);
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_relics = false;
+                        }
+                        if ui.button(tr("Save")).clicked() {
+                            self.relic_inventory
+                                .relics
+                                .retain(|r| !r.name.trim().is_empty());
+                            self.relic_inventory.sort();
+
+                            if let Err(e) = self.relic_inventory.write_to_file() {
+                                warn!("Cannot write relics file: {}", e);
+                            }
+
+                            self.app.data.write().relics = self.relic_inventory.clone();
+                            self.show_relics = false;
+                        }
+                    },
+                );
+            });
+    }
+}
@@ -0,0 +1,60 @@
+use super::UI;
+use crate::clock;
+use crate::i18n::tr;
+use crate::replay::REPLAY_SPEEDS;
+
+use eframe::egui::{ComboBox, Context, TopBottomPanel};
+
+impl UI {
+    /// Render the replay controls at the bottom of the window.
+    pub(super) fn render_replay_bar(&mut self, ctx: &Context) {
+        let replay = match &self.app.replay {
+            Some(replay) => replay.clone(),
+            None => return,
+        };
+        let replay_clock = replay.clock();
+
+        TopBottomPanel::bottom("replay_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui
+                    .button("⏮")
+                    .on_hover_text(tr("Previous snapshot"))
+                    .clicked()
+                {
+                    replay.step(false);
+                }
+                let paused = replay_clock.is_paused();
+                if ui
+                    .button(if paused { "⏵" } else { "⏸" })
+                    .on_hover_text(if paused { tr("Play") } else { tr("Pause") })
+                    .clicked()
+                {
+                    replay_clock.set_paused(!paused);
+                }
+                if ui.button("⏭").on_hover_text(tr("Next snapshot")).clicked() {
+                    replay.step(true);
+                }
+
+                let speed = replay_clock.speed();
+                ComboBox::from_id_source("replay_speed")
+                    .selected_text(format!("{}x", speed))
+                    .width(64.0)
+                    .show_ui(ui, |ui| {
+                        for s in REPLAY_SPEEDS {
+                            if ui.selectable_label(speed == s, format!("{}x", s)).clicked() {
+                                replay_clock.set_speed(s);
+                            }
+                        }
+                    });
+
+                ui.label(format!(
+                    "{} {} ({}/{})",
+                    tr("Replay"),
+                    clock::local_now().format("%Y-%m-%d %H:%M:%S"),
+                    replay.current() + 1,
+                    replay.snapshots().len()
+                ));
+            });
+        });
+    }
+}
@@ -0,0 +1,243 @@
+use super::countdowns::countdowns_editor;
+use super::notifications::notification_settings_ui;
+use super::{filter_ui, no_tray_text, ui_style, UI};
+use crate::filters::{FilterOptions, FissureFilter};
+use crate::i18n::{self, tr, Language};
+use crate::theme::ThemeKind;
+use crate::voidrat::{is_data_dir, Storage, MIN_REFRESH_INTERVAL};
+use crate::widgets::UiExt;
+
+use eframe::egui::{
+    Align, ComboBox, Context, Direction, DragValue, Grid, Layout, Pos2, ScrollArea, Slider,
+    TextEdit, Window,
+};
+
+/// Tabs of the settings window.
+#[derive(PartialEq, Clone)]
+pub(super) enum SettingsTab {
+    General,
+    Notifications,
+    Filters,
+    Appearance,
+    Network,
+}
+
+impl UI {
+    /// Start editing the settings from the saved ones.
+    pub(super) fn open_settings(&mut self) {
+        self.settings = self.app.data.read().storage.clone();
+        self.settings_opened = self.settings.clone();
+        self.settings_status = None;
+        self.show_settings = true;
+    }
+
+    /// Render every persisted option in tabs.
+    /// Nothing changes until the settings are valid and applied.
+    pub(super) fn render_settings_window(&mut self, ctx: &Context) {
+        let (missions, options) = {
+            let data = self.app.data.read();
+            (
+                FilterOptions::new(&data.fissures, &FissureFilter::default()).missions,
+                FilterOptions::new(&data.fissures, &self.settings.fissure_filter),
+            )
+        };
+        let has_tray = self.tray.is_some();
+        let mut apply = false;
+
+        Window::new(tr("Settings"))
+            .default_width(420.0)
+            .min_width(420.0)
+            .default_pos(Pos2::new(24.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let tab = &mut self.settings_tab;
+                    ui.toggled_button(tab, SettingsTab::General, tr("General"));
+                    ui.toggled_button(tab, SettingsTab::Notifications, tr("Notifications"));
+                    ui.toggled_button(tab, SettingsTab::Filters, tr("Filters"));
+                    ui.toggled_button(tab, SettingsTab::Appearance, tr("Appearance"));
+                    ui.toggled_button(tab, SettingsTab::Network, tr("Network"));
+                });
+                ui.separator();
+
+                let settings = &mut self.settings;
+                ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| match self.settings_tab {
+                        SettingsTab::General => {
+                            Grid::new("settings_general_grid").show(ui, |ui| {
+                                ui.label(tr("Update interval"));
+                                ui.add(
+                                    DragValue::new(&mut settings.update_cooldown)
+                                        .clamp_range(MIN_REFRESH_INTERVAL..=3600)
+                                        .suffix(" s"),
+                                );
+                                ui.end_row();
+
+                                ui.label(tr("Data directory"));
+                                ui.add(
+                                    TextEdit::singleline(&mut settings.data_dir)
+                                        .hint_text("data")
+                                        .desired_width(200.0),
+                                )
+                                .on_hover_text(tr("Used after a restart"));
+                                ui.end_row();
+
+                                if !is_data_dir(&settings.data_dir) {
+                                    ui.label("");
+                                    ui.weak(tr("Restart to use it. Relics, the data pack and the history are not moved, copy them over first."));
+                                    ui.end_row();
+                                }
+
+                                ui.label(tr("Keep history for"));
+                                ui.add(
+                                    DragValue::new(&mut settings.history_retention_days)
+                                        .clamp_range(1..=3650)
+                                        .suffix(format!(" {}", tr("days"))),
+                                );
+                                ui.end_row();
+                            });
+                            ui.add_space(8.0);
+                            ui.add_enabled_ui(has_tray, |ui| {
+                                ui.checkbox(
+                                    &mut settings.minimize_to_tray,
+                                    tr("Minimize to tray on close"),
+                                );
+                                ui.checkbox(
+                                    &mut settings.start_minimized,
+                                    tr("Start minimized to tray"),
+                                );
+                            })
+                            .response
+                            .on_disabled_hover_text(no_tray_text());
+                            ui.checkbox(&mut settings.overlay.enabled, tr("Start as the overlay"));
+                        }
+                        SettingsTab::Notifications => {
+                            notification_settings_ui(
+                                ui,
+                                &mut settings.notification_settings,
+                                &missions,
+                            );
+                        }
+                        SettingsTab::Filters => {
+                            filter_ui(ui, &mut settings.fissure_filter, &options);
+                        }
+                        SettingsTab::Appearance => {
+                            Grid::new("settings_appearance_grid").show(ui, |ui| {
+                                ui.label(tr("Language"));
+                                ComboBox::from_id_source("settings_language")
+                                    .selected_text(settings.language.to_string())
+                                    .show_ui(ui, |ui| {
+                                        for language in Language::ALL {
+                                            ui.selectable_value(
+                                                &mut settings.language,
+                                                language,
+                                                language.to_string(),
+                                            );
+                                        }
+                                    });
+                                ui.end_row();
+
+                                ui.label(tr("Theme"));
+                                ui.horizontal(|ui| {
+                                    ComboBox::from_id_source("settings_theme")
+                                        .selected_text(tr(&settings.theme.to_string()))
+                                        .show_ui(ui, |ui| {
+                                            for kind in ThemeKind::ALL {
+                                                ui.selectable_value(
+                                                    &mut settings.theme,
+                                                    kind,
+                                                    tr(&kind.to_string()),
+                                                );
+                                            }
+                                        });
+                                    if ui.button(tr("Edit custom theme")).clicked() {
+                                        self.custom_theme = settings.custom_theme;
+                                        self.show_theme_editor = true;
+                                    }
+                                });
+                                ui.end_row();
+
+                                ui.label(tr("Overlay opacity"));
+                                ui.add(Slider::new(&mut settings.overlay.opacity, 0.2..=1.0));
+                                ui.end_row();
+                            });
+                            ui.add_space(8.0);
+                            ui.label(tr("Countdown colors"));
+                            countdowns_editor(ui, "settings_countdowns", &mut settings.countdowns);
+                        }
+                        SettingsTab::Network => {
+                            Grid::new("settings_network_grid").show(ui, |ui| {
+                                ui.label(tr("Data pack URL"));
+                                ui.add(
+                                    TextEdit::singleline(&mut settings.data_pack_url)
+                                        .hint_text("https://")
+                                        .desired_width(240.0),
+                                );
+                                ui.end_row();
+
+                                ui.label(tr("Request timeout"));
+                                ui.add(
+                                    DragValue::new(&mut settings.request_timeout)
+                                        .clamp_range(1..=300)
+                                        .suffix(" s"),
+                                );
+                                ui.end_row();
+                            });
+                        }
+                    });
+
+                if let Some(status) = &self.settings_status {
+                    ui.separator();
+                    ui.label(status);
+                }
+
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Cancel")).clicked() {
+                            self.show_settings = false;
+                        }
+                        if ui.button(tr("Apply")).clicked() {
+                            apply = true;
+                        }
+                        if ui.button(tr("Reset to defaults")).clicked() {
+                            let edited = self.settings.clone();
+                            self.settings.apply_settings(&Storage::default(), &edited);
+                            self.settings_status = None;
+                        }
+                    },
+                );
+            });
+
+        if apply {
+            self.apply_settings(ctx);
+        }
+    }
+
+    /// Save the edited settings if they are valid and use them right away.
+    pub(super) fn apply_settings(&mut self, ctx: &Context) {
+        if let Err(e) = self.settings.validate() {
+            self.settings_status = Some(e);
+            return;
+        }
+
+        self.settings.countdowns.sort();
+        let mut data = self.app.data.write();
+        data.storage
+            .apply_settings(&self.settings, &self.settings_opened);
+        data.storage.save();
+        self.settings_opened = self.settings.clone();
+
+        i18n::set_language(data.storage.language);
+        ui_style(ctx, data.storage.theme.theme(&data.storage.custom_theme));
+        // The other windows edit their own copies.
+        self.custom_theme = data.storage.custom_theme;
+        self.countdowns = data.storage.countdowns.clone();
+        self.notification_settings = data.storage.notification_settings.clone();
+        self.data_pack_url = data.storage.data_pack_url.clone();
+        self.history_retention_days = data.storage.history_retention_days;
+        self.settings_status = Some(tr("Settings applied"));
+    }
+}
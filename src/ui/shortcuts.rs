@@ -0,0 +1,70 @@
+use super::UI;
+use crate::i18n::tr;
+use crate::keybindings::{Action, Keybindings};
+
+use eframe::egui::{Align, Button, Context, Direction, Grid, Layout, Pos2, Window};
+
+impl UI {
+    /// Render the key of every action, click a key to bind another one.
+    pub(super) fn render_shortcuts_window(&mut self, ctx: &Context) {
+        let bindings = self.keybindings();
+        let mut changed = None;
+
+        Window::new(tr("Keyboard shortcuts"))
+            .default_width(320.0)
+            .min_width(320.0)
+            .default_pos(Pos2::new(24.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                Grid::new("shortcuts_grid").striped(true).show(ui, |ui| {
+                    for action in Action::ALL {
+                        ui.label(tr(&action.to_string()));
+
+                        let text = if self.capturing == Some(action) {
+                            tr("Press a key…")
+                        } else {
+                            bindings
+                                .get(action)
+                                .map_or_else(|| tr("None"), |b| b.to_string())
+                        };
+                        if ui.button(text).clicked() {
+                            self.capturing = Some(action);
+                        }
+                        if ui
+                            .add_enabled(bindings.get(action).is_some(), Button::new("✖"))
+                            .clicked()
+                        {
+                            changed = Some((action, None));
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                ui.weak(tr(
+                    "Keys without Ctrl or Alt do not work while typing, except F1 to F12.",
+                ));
+
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_shortcuts = false;
+                            self.capturing = None;
+                        }
+                        if ui.button(tr("Defaults")).clicked() {
+                            let mut data = self.app.data.write();
+                            data.storage.keybindings = Keybindings::default();
+                            data.storage.save();
+                        }
+                    },
+                );
+            });
+
+        if let Some((action, binding)) = changed {
+            let mut data = self.app.data.write();
+            data.storage.keybindings.set(action, binding);
+            data.storage.save();
+        }
+    }
+}
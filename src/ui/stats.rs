@@ -0,0 +1,211 @@
+use super::{ARCHIVE_ROWS, UI};
+use crate::i18n::{self, tr};
+use crate::parsers::FissureTier;
+use crate::stats::{day_to_string, Stats};
+use crate::widgets::UiExt;
+
+use eframe::egui::widgets::plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints};
+use eframe::egui::{Align, Color32, Context, Direction, Grid, Layout, Pos2, ScrollArea, Window};
+
+/// Tabs of the statistics window.
+#[derive(PartialEq, Clone)]
+pub(super) enum StatsView {
+    Tiers,
+    Missions,
+    Lifetimes,
+    Nodes,
+    Invasions,
+}
+
+impl UI {
+    /// Collect the statistics from the archive again.
+    pub(super) fn collect_stats(&mut self) {
+        if let Err(e) = self.open_archive() {
+            self.stats_status = Some(e);
+            return;
+        }
+
+        match Stats::collect(self.archive.as_ref().unwrap()) {
+            Ok(stats) => {
+                self.stats = stats;
+                self.stats_status = None;
+            }
+            Err(e) => self.stats_status = Some(e.to_string()),
+        }
+    }
+
+    pub(super) fn render_stats_window(&mut self, ctx: &Context) {
+        Window::new(tr("Statistics"))
+            .default_width(520.0)
+            .min_width(520.0)
+            .default_pos(Pos2::new(24.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.toggled_button(&mut self.stats_view, StatsView::Tiers, tr("Tiers"));
+                    ui.toggled_button(&mut self.stats_view, StatsView::Missions, tr("Missions"));
+                    ui.toggled_button(&mut self.stats_view, StatsView::Lifetimes, tr("Lifetimes"));
+                    ui.toggled_button(&mut self.stats_view, StatsView::Nodes, tr("Nodes"));
+                    ui.toggled_button(&mut self.stats_view, StatsView::Invasions, tr("Invasions"));
+                });
+                ui.add_space(8.0);
+
+                let stats = &self.stats;
+                match self.stats_view {
+                    StatsView::Tiers => {
+                        ui.label(tr("Fissures per day"));
+                        Plot::new("stats_tiers_plot")
+                            .height(240.0)
+                            .legend(Legend::default())
+                            .include_y(0.0)
+                            .x_axis_formatter(|day, _| day_to_string(day.round() as i64))
+                            .show(ui, |plot_ui| {
+                                for (tier, days) in &stats.tiers_per_day {
+                                    let points = days
+                                        .iter()
+                                        .map(|(day, count)| [*day as f64, *count as f64])
+                                        .collect::<Vec<[f64; 2]>>();
+                                    plot_ui.line(
+                                        Line::new(PlotPoints::from(points))
+                                            .name(i18n::tier(&tier.to_string())),
+                                    );
+                                }
+                            });
+                    }
+                    StatsView::Missions => {
+                        ui.horizontal(|ui| {
+                            for tier in FissureTier::ALL {
+                                let text = i18n::tier(&tier.to_string());
+                                ui.toggled_button(&mut self.stats_tier, tier, text);
+                            }
+                        });
+
+                        let missions = stats
+                            .missions
+                            .get(&self.stats_tier)
+                            .map_or(&[][..], |m| &m[..]);
+                        let bars = missions
+                            .iter()
+                            .enumerate()
+                            .map(|(i, (mission, count))| {
+                                Bar::new(i as f64, *count as f64).name(i18n::mission(mission))
+                            })
+                            .collect();
+
+                        Plot::new("stats_missions_plot")
+                            .height(160.0)
+                            .include_y(0.0)
+                            .show_x(false)
+                            .show(ui, |plot_ui| plot_ui.bar_chart(BarChart::new(bars)));
+
+                        ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                            Grid::new("stats_missions_grid")
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for (mission, count) in missions {
+                                        ui.label(i18n::mission(mission));
+                                        ui.label(count.to_string());
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                    StatsView::Lifetimes => {
+                        ui.label(tr("Average fissure lifetime in minutes"));
+                        let bars = stats
+                            .lifetimes
+                            .iter()
+                            .enumerate()
+                            .map(|(i, (tier, minutes))| {
+                                Bar::new(i as f64, *minutes).name(i18n::tier(&tier.to_string()))
+                            })
+                            .collect();
+
+                        Plot::new("stats_lifetimes_plot")
+                            .height(200.0)
+                            .include_y(0.0)
+                            .show_x(false)
+                            .show(ui, |plot_ui| plot_ui.bar_chart(BarChart::new(bars)));
+
+                        Grid::new("stats_lifetimes_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (tier, minutes) in &stats.lifetimes {
+                                    ui.label(i18n::tier(&tier.to_string()));
+                                    ui.label(format!("{:.0} {}", minutes, tr("min")));
+                                    ui.end_row();
+                                }
+                            });
+                    }
+                    StatsView::Nodes => {
+                        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                            Grid::new("stats_nodes_grid").striped(true).show(ui, |ui| {
+                                ui.strong(tr("Node"));
+                                ui.strong(tr("Mission"));
+                                ui.strong(tr("Fissures"));
+                                ui.strong(tr("Steel Path"));
+                                ui.strong(tr("Void Storms"));
+                                ui.end_row();
+
+                                for n in stats.nodes.iter().take(ARCHIVE_ROWS) {
+                                    if n.is_void_capture() {
+                                        ui.colored_label(Color32::GOLD, &n.node);
+                                    } else {
+                                        ui.label(&n.node);
+                                    }
+                                    ui.label(&n.mission);
+                                    ui.label(n.total.to_string());
+                                    ui.label(n.hard.to_string());
+                                    ui.label(n.storm.to_string());
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                    }
+                    StatsView::Invasions => {
+                        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                            Grid::new("stats_invasions_grid")
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.strong(tr("Reward"));
+                                    ui.strong(tr("Invasions"));
+                                    ui.end_row();
+
+                                    for (reward, count) in
+                                        stats.invasion_rewards.iter().take(ARCHIVE_ROWS)
+                                    {
+                                        ui.label(reward);
+                                        ui.label(count.to_string());
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                }
+
+                if let Some(status) = &self.stats_status {
+                    ui.label(status);
+                }
+
+                // Buttons
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_stats = false;
+                        }
+                        if ui.button(tr("Export CSV")).clicked() {
+                            self.stats_status = Some(match self.stats.write_csv() {
+                                Ok(dir) => format!("{}: {}", tr("Exported to"), dir.display()),
+                                Err(e) => e.to_string(),
+                            });
+                        }
+                        if ui.button(tr("⟳ Refresh")).clicked() {
+                            self.collect_stats();
+                        }
+                    },
+                );
+            });
+    }
+}
@@ -0,0 +1,100 @@
+use super::{ui_style, UI};
+use crate::i18n::tr;
+use crate::theme::ThemeKind;
+
+use eframe::egui::{Align, Context, Direction, Grid, Layout, Pos2, Window};
+
+impl UI {
+    /// Use the theme of the `kind` and save it.
+    pub(super) fn set_theme(&mut self, ctx: &Context, kind: ThemeKind) {
+        let mut data = self.app.data.write();
+        data.storage.theme = kind;
+        ui_style(ctx, kind.theme(&data.storage.custom_theme));
+        data.storage.save();
+    }
+
+    /// Render the colors of the custom theme.
+    /// Changes are shown right away, saving also switches to the custom theme.
+    pub(super) fn render_theme_window(&mut self, ctx: &Context) {
+        let before = self.custom_theme;
+
+        Window::new(tr("Custom theme"))
+            .default_width(320.0)
+            .min_width(320.0)
+            .default_pos(Pos2::new(24.0, 100.0))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Start from"));
+                    for kind in [ThemeKind::Light, ThemeKind::Dark, ThemeKind::HighContrast] {
+                        if ui.button(tr(&kind.to_string())).clicked() {
+                            self.custom_theme = kind.theme(&self.custom_theme);
+                        }
+                    }
+                });
+                ui.checkbox(&mut self.custom_theme.dark, tr("Dark base"));
+
+                let theme = &mut self.custom_theme;
+                Grid::new("custom_theme_grid").show(ui, |ui| {
+                    for (text, rgb) in [
+                        ("Background", &mut theme.background),
+                        ("Text", &mut theme.text),
+                        ("Border", &mut theme.border),
+                        ("Button", &mut theme.button),
+                        ("Button border", &mut theme.button_border),
+                        ("Selection", &mut theme.selection),
+                        ("Selection border", &mut theme.selection_border),
+                        ("Text field", &mut theme.extreme_background),
+                        ("Badge text", &mut theme.badge_text),
+                        ("Expired text", &mut theme.expired_text),
+                    ] {
+                        ui.label(tr(text));
+                        ui.color_edit_button_srgb(rgb);
+                        ui.end_row();
+                    }
+
+                    for (text, badge) in [
+                        ("Urgent", &mut theme.urgent),
+                        ("Soon", &mut theme.soon),
+                        ("Later", &mut theme.later),
+                        ("Plenty", &mut theme.plenty),
+                        ("Void Capture", &mut theme.void_capture),
+                        ("Expired", &mut theme.expired),
+                        ("Neutral", &mut theme.neutral),
+                    ] {
+                        ui.label(tr(text));
+                        ui.horizontal(|ui| {
+                            ui.color_edit_button_srgb(&mut badge.fill);
+                            ui.color_edit_button_srgb(&mut badge.border);
+                        });
+                        ui.end_row();
+                    }
+                });
+
+                ui.with_layout(
+                    Layout::from_main_dir_and_cross_align(Direction::RightToLeft, Align::RIGHT),
+                    |ui| {
+                        if ui.button(tr("Close")).clicked() {
+                            self.show_theme_editor = false;
+                            // Back to the saved theme.
+                            let data = self.app.data.read();
+                            ui_style(ctx, data.storage.theme.theme(&data.storage.custom_theme));
+                        }
+                        if ui.button(tr("Save")).clicked() {
+                            self.show_theme_editor = false;
+                            self.app.data.write().storage.custom_theme = self.custom_theme;
+                            self.set_theme(ctx, ThemeKind::Custom);
+                            // Keep an open settings window from undoing the theme.
+                            self.settings.custom_theme = self.custom_theme;
+                            self.settings.theme = ThemeKind::Custom;
+                        }
+                    },
+                );
+            });
+
+        if self.show_theme_editor && self.custom_theme != before {
+            ui_style(ctx, self.custom_theme);
+        }
+    }
+}
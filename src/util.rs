@@ -1,5 +1,4 @@
 use chrono::Duration;
-use eframe::egui::Color32;
 use egui_extras::RetainedImage;
 use log::warn;
 use rust_embed::RustEmbed;
@@ -37,37 +36,6 @@ pub fn duration_to_string(dur: &Duration) -> String {
     }
 }
 
-/// Return background and border color based on the duration left.
-///
-/// https://yeun.github.io/open-color/ingredients.html
-pub fn time_left_color(dur: &Duration) -> (Color32, Color32) {
-    // let seconds = dur.num_seconds() % 60;
-    let minutes = (dur.num_seconds() / 60) % 60;
-    let hours = (dur.num_seconds() / 60) / 60;
-
-    if hours == 0 && minutes < 10 {
-        (
-            Color32::from_rgb(255, 227, 227), // Red 1
-            Color32::from_rgb(255, 168, 168), // Red 3
-        )
-    } else if hours == 0 && minutes < 20 {
-        (
-            Color32::from_rgb(255, 243, 191), // Yellow 1
-            Color32::from_rgb(255, 224, 102), // Yellow 3
-        )
-    } else if hours == 0 && minutes < 40 {
-        (
-            Color32::from_rgb(211, 249, 216), // Green 1
-            Color32::from_rgb(140, 233, 154), // Green 3
-        )
-    } else {
-        (
-            Color32::from_rgb(208, 235, 255), // Blue 1
-            Color32::from_rgb(116, 192, 252), // Blue 3
-        )
-    }
-}
-
 pub fn split_pascal_case(value: &str) -> String {
     let mut idxs = vec![];
     let mut copy = value.to_string();
@@ -85,3 +53,18 @@ pub fn split_pascal_case(value: &str) -> String {
     // Return the finished string.
     copy
 }
+
+/// Returns the fields as a single CSV line, quoting the fields when needed.
+pub fn csv_line(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|f| {
+            if f.contains(',') || f.contains('"') || f.contains('\n') {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
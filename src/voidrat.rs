@@ -2,40 +2,79 @@ use crate::parsers::world_state::WorldState;
 use crate::parsers::{CetusCycle, Fissure, Invasion, Reward, TennoParser};
 
 use bincode::{config, decode_from_std_read, encode_into_std_write};
-use chrono::{DateTime, Duration, Local, Utc};
+use chrono::{DateTime, Duration, Utc};
 use log::{debug, warn};
 use parking_lot::RwLock;
 
 use std::env::current_dir;
-use std::fs::{create_dir, File};
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
 use std::io::{BufWriter, Cursor, Seek, SeekFrom, Write};
 
-use std::path::PathBuf;
-
+use std::path::{Path, PathBuf};
+
+use crate::archive::Archive;
+use crate::clock;
+use crate::countdown::Countdowns;
+use crate::diff::{EventBus, WorldDiff, WorldEvent};
+use crate::filters::{FilterPreset, FissureFilter};
+use crate::i18n::Language;
+use crate::keybindings::Keybindings;
+use crate::notifications::{
+    Delivery, Notification, NotificationHistory, NotificationKey, NotificationRule,
+    NotificationSettings,
+};
+use crate::overlay::{Overlay, CLICK_THROUGH_ARG};
 use crate::parsers::warframestat::WarframeStat;
-use crate::ui::FISSURE_FILTER_LEN;
+use crate::relics::RelicInventory;
+use crate::replay::{self, Replay};
+use crate::sorting::{SortKey, TableSorting};
+use crate::theme::{Theme, ThemeKind};
 use crate::Resources;
 use filetime::FileTime;
 use rodio::{Decoder, OutputStream, Source};
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::{mpsc, Arc};
 use std::thread::JoinHandle;
+use std::time::Instant;
 use std::{fs, thread};
 
-const STORAGE_FILE: &str = "voidrat.storage";
-const DATA_PATH: &str = "data";
+pub const STORAGE_FILE: &str = "voidrat.storage";
+/// Layout of `Storage` in the storage file, bump it when a field changes.
+const STORAGE_VERSION: u32 = 1;
+const DEFAULT_DATA_PATH: &str = "data";
 const WORLD_STATE_DATA_PATH: &str = "world_state.json";
 const WORLD_STATE_URL: &str = "https://content.warframe.com/dynamic/worldState.php";
+/// Manual refreshes wait at least this many seconds after the last update.
+pub const MIN_REFRESH_INTERVAL: i64 = 30;
 
-#[derive(Debug, Clone, bincode::Encode, bincode::Decode)]
-pub struct Notification {
-    pub timestamp: i64,
+/// Directory of the downloaded data, `None` for the default one.
+static DATA_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Keep the downloaded data in `dir` from now on, the default directory if it is empty.
+pub fn set_data_dir(dir: &str) {
+    let dir = dir.trim();
+    *DATA_DIR.write() = (!dir.is_empty()).then(|| PathBuf::from(dir));
 }
 
-impl Notification {
-    pub fn new(timestamp: i64) -> Self {
-        Notification { timestamp }
-    }
+/// Returns true if `dir`, as entered in the settings, is the data directory in use.
+pub fn is_data_dir(dir: &str) -> bool {
+    let dir = dir.trim();
+
+    data_path()
+        == Path::new(if dir.is_empty() {
+            DEFAULT_DATA_PATH
+        } else {
+            dir
+        })
+}
+
+/// Returns the directory of the downloaded data.
+pub fn data_path() -> PathBuf {
+    DATA_DIR
+        .read()
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DATA_PATH))
 }
 
 /// Persistently keeps track when the data was last updated.
@@ -45,12 +84,48 @@ pub struct Storage {
     pub update_cooldown: i64,
     /// When the last fetch happened in seconds.
     pub last_update: i64,
-
-    pub notified: Vec<Notification>,
-
-    pub noti_fissure_void_capture: bool,
-    pub noti_invasion_epic: bool,
-    pub fissure_filter: [bool; FISSURE_FILTER_LEN],
+    /// Fired notifications and muted notification rules.
+    pub notifications: NotificationHistory,
+
+    /// Which notifications to deliver and how.
+    pub notification_settings: NotificationSettings,
+    /// Which fissures are shown.
+    pub fissure_filter: FissureFilter,
+    /// Saved fissure filters.
+    pub filter_presets: Vec<FilterPreset>,
+    /// Do not disturb is on until this time in seconds.
+    pub dnd_until: Option<i64>,
+    /// Fissures that get a reminder before they expire.
+    pub watched_fissures: Vec<NotificationKey>,
+    /// How many days fissures and invasions are kept in the archive.
+    pub history_retention_days: u32,
+    /// Fissures and invasions the user has already looked at.
+    pub seen: Vec<NotificationKey>,
+    /// Sorting and grouping of the fissure table.
+    pub fissure_sorting: TableSorting,
+    /// Sorting and grouping of the void storm table.
+    pub storm_sorting: TableSorting,
+    /// Sorting and grouping of the invasion table.
+    pub invasion_sorting: TableSorting,
+    /// Where the data pack is downloaded from.
+    pub data_pack_url: String,
+    /// Language of the UI and the game names.
+    pub language: Language,
+    pub theme: ThemeKind,
+    /// Colors of the custom theme.
+    pub custom_theme: Theme,
+    /// Countdown badge colors of every view.
+    pub countdowns: Countdowns,
+    pub overlay: Overlay,
+    /// Hide the window to the tray instead of quitting when it is closed.
+    pub minimize_to_tray: bool,
+    /// Start with only the tray icon.
+    pub start_minimized: bool,
+    pub keybindings: Keybindings,
+    /// Directory of the downloaded data, empty for the default one.
+    pub data_dir: String,
+    /// How many seconds to wait for a download.
+    pub request_timeout: u64,
 }
 
 impl Default for Storage {
@@ -58,10 +133,31 @@ impl Default for Storage {
         Self {
             update_cooldown: 300,
             last_update: 0,
-            notified: vec![],
-            noti_fissure_void_capture: false,
-            noti_invasion_epic: false,
-            fissure_filter: [true; FISSURE_FILTER_LEN],
+            notifications: NotificationHistory::default(),
+            notification_settings: NotificationSettings::default(),
+            fissure_filter: FissureFilter::default(),
+            filter_presets: vec![],
+            dnd_until: None,
+            watched_fissures: vec![],
+            history_retention_days: 90,
+            seen: vec![],
+            fissure_sorting: TableSorting::default(),
+            storm_sorting: TableSorting::default(),
+            invasion_sorting: TableSorting {
+                key: SortKey::Time,
+                ..TableSorting::default()
+            },
+            data_pack_url: String::new(),
+            language: Language::English,
+            theme: ThemeKind::Light,
+            custom_theme: Theme::light(),
+            countdowns: Countdowns::default(),
+            overlay: Overlay::default(),
+            minimize_to_tray: false,
+            start_minimized: false,
+            keybindings: Keybindings::default(),
+            data_dir: String::new(),
+            request_timeout: 30,
         }
     }
 }
@@ -83,21 +179,50 @@ impl Storage {
         }
 
         // Open the storage file and try to decode it
-        match File::open(&file_path) {
-            Ok(mut f) => match decode_from_std_read(&mut f, config::standard()) {
-                Ok(s) => s,
-                Err(_) => {
-                    // If there was an error loading `Storage` from file then
-                    // use defaults instead.
-                    Self::default()
-                }
-            },
+        let decoded = match File::open(&file_path) {
+            Ok(mut f) => Self::decode(&mut f),
             Err(e) => panic!("{}", e),
+        };
+
+        // If there was an error loading `Storage` from file then keep the file
+        // and use defaults instead.
+        decoded.unwrap_or_else(|e| {
+            let backup = file_path.with_extension("storage.bak");
+            warn!(
+                "Cannot load {}, using the defaults and keeping the old file as {}: {}",
+                file,
+                backup.display(),
+                e
+            );
+            if let Err(e) = fs::copy(&file_path, &backup) {
+                warn!("Cannot back up {}: {}", file, e);
+            }
+
+            Self::default()
+        })
+    }
+
+    /// Decode the version and then the `Storage` of that version.
+    fn decode(reader: &mut impl std::io::Read) -> Result<Self, String> {
+        let version: u32 =
+            decode_from_std_read(reader, config::standard()).map_err(|e| e.to_string())?;
+
+        match version {
+            STORAGE_VERSION => {
+                decode_from_std_read(reader, config::standard()).map_err(|e| e.to_string())
+            }
+            // Older layouts would be migrated here.
+            _ => Err(format!("unknown storage version {}", version)),
         }
     }
 
     /// Encode and write to file.
+    /// Nothing is written during a replay.
     pub fn write_to_file(&self) -> Result<usize, bincode::error::EncodeError> {
+        if clock::is_virtual() {
+            return Ok(0);
+        }
+
         let path = current_dir().unwrap();
         let file_path = path.join(STORAGE_FILE);
         let f = File::create(&file_path).expect("Cannot create file!");
@@ -105,22 +230,137 @@ impl Storage {
 
         debug!("Writing to file..");
 
-        encode_into_std_write(self, &mut writer, config::standard())
+        let version = encode_into_std_write(STORAGE_VERSION, &mut writer, config::standard())?;
+        Ok(version + encode_into_std_write(self, &mut writer, config::standard())?)
+    }
+
+    /// Returns why the settings cannot be used, if they cannot.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.update_cooldown < MIN_REFRESH_INTERVAL {
+            return Err(format!(
+                "The update interval must be at least {} seconds",
+                MIN_REFRESH_INTERVAL
+            ));
+        }
+        if !self.data_dir.trim().is_empty() && PathBuf::from(self.data_dir.trim()).is_file() {
+            return Err("The data directory is a file".to_string());
+        }
+        let url = self.data_pack_url.trim();
+        if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err("The data pack URL must start with http:// or https://".to_string());
+        }
+        if self.history_retention_days == 0 {
+            return Err("History must be kept for at least one day".to_string());
+        }
+        if self.request_timeout == 0 {
+            return Err("The request timeout must be at least one second".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Take the options of the settings window that differ between `settings` and `opened`,
+    /// the storage the window started from, so the ones changed elsewhere meanwhile stay.
+    /// History, presets, sorting and the other state stay as they are.
+    pub fn apply_settings(&mut self, settings: &Storage, opened: &Storage) {
+        macro_rules! take {
+            ($($field:ident).+) => {
+                if settings.$($field).+ != opened.$($field).+ {
+                    self.$($field).+ = settings.$($field).+.clone();
+                }
+            };
+        }
+
+        take!(update_cooldown);
+        take!(data_dir);
+        take!(start_minimized);
+        take!(minimize_to_tray);
+        take!(overlay.enabled);
+        take!(overlay.opacity);
+        take!(language);
+        take!(notification_settings);
+        take!(fissure_filter);
+        take!(theme);
+        take!(custom_theme);
+        take!(countdowns);
+        take!(data_pack_url);
+        take!(history_retention_days);
+        take!(request_timeout);
+
+        self.data_dir = self.data_dir.trim().to_string();
+        self.data_pack_url = self.data_pack_url.trim().to_string();
     }
 
     /// Returns true if enough time has passed since the last update.
     pub fn can_update(&self) -> bool {
-        self.last_update + self.update_cooldown < Local::now().timestamp()
+        self.last_update + self.update_cooldown < clock::now().timestamp()
     }
 
-    /// Next update can happen in this many seconds. Debug use.
+    /// Next update can happen in this many seconds.
     pub fn next_update(&self) -> i64 {
-        (self.last_update + self.update_cooldown) - Local::now().timestamp()
+        (self.last_update + self.update_cooldown) - clock::now().timestamp()
     }
 
-    pub fn save_notification(&mut self, a: bool, b: bool) {
-        self.noti_fissure_void_capture = a;
-        self.noti_invasion_epic = b;
+    /// Returns true if the fissure filter lets the fissure through.
+    pub fn shows_fissure(&self, fissure: &Fissure) -> bool {
+        self.fissure_filter.shows(fissure)
+    }
+
+    /// Save the current fissure filter as a preset, replacing the preset with the same name.
+    pub fn save_filter_preset(&mut self, name: &str) {
+        let preset = FilterPreset {
+            name: name.trim().to_string(),
+            filter: self.fissure_filter.clone(),
+        };
+
+        match self
+            .filter_presets
+            .iter_mut()
+            .find(|p| p.name == preset.name)
+        {
+            Some(existing) => *existing = preset,
+            None => self.filter_presets.push(preset),
+        }
+
+        self.save();
+    }
+
+    /// Returns true if do not disturb is currently on.
+    pub fn dnd_active(&self) -> bool {
+        self.dnd_until
+            .is_some_and(|until| until > clock::now().timestamp())
+    }
+
+    /// Start or stop watching the fissure with the `key`.
+    pub fn toggle_watched(&mut self, key: NotificationKey) {
+        if let Some(i) = self.watched_fissures.iter().position(|k| *k == key) {
+            self.watched_fissures.remove(i);
+        } else {
+            self.watched_fissures.push(key);
+        }
+
+        self.save();
+    }
+
+    /// Returns how a notification for the `rule` should be delivered right now.
+    pub fn delivery(&self, rule: NotificationRule) -> Delivery {
+        if self.dnd_active() || self.notifications.is_muted(rule) {
+            return Delivery::Suppressed;
+        }
+
+        let now = clock::local_now();
+
+        self.notification_settings
+            .quiet_hours
+            .iter()
+            .filter(|q| q.contains(now))
+            .map(|q| q.delivery)
+            .max()
+            .unwrap_or(Delivery::Audio)
+    }
+
+    pub fn save_notification(&mut self, settings: NotificationSettings) {
+        self.notification_settings = settings;
 
         self.write_to_file().expect("Cannot write to storage file.");
     }
@@ -128,6 +368,48 @@ impl Storage {
     pub fn save(&mut self) {
         self.write_to_file().expect("Cannot write to storage file.");
     }
+
+    /// Returns the seconds until a manual refresh is allowed, 0 if it is allowed now.
+    pub fn refresh_wait(&self) -> i64 {
+        (self.last_update + MIN_REFRESH_INTERVAL - clock::now().timestamp()).max(0)
+    }
+}
+
+/// Command line options.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Play back the world state snapshots in this directory instead of fetching new data.
+    pub replay: Option<PathBuf>,
+    /// Replay speed, virtual seconds per real second.
+    pub speed: Option<f64>,
+    /// Keep every fetched world state in this directory for a later replay.
+    pub record: Option<PathBuf>,
+    /// Pass the clicks through the overlay, only given when the overlay starts itself again.
+    pub click_through: bool,
+    /// Time parsing this world state file instead of starting the app.
+    #[cfg(feature = "bench")]
+    pub bench: Option<PathBuf>,
+}
+
+impl Options {
+    /// Parse `--replay <dir>`, `--speed <n>`, `--record <dir>` and, with the bench feature, `--bench <file>`.
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut options = Options::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--replay" => options.replay = args.next().map(PathBuf::from),
+                "--record" => options.record = args.next().map(PathBuf::from),
+                #[cfg(feature = "bench")]
+                "--bench" => options.bench = args.next().map(PathBuf::from),
+                "--speed" => options.speed = args.next().and_then(|s| s.parse().ok()),
+                CLICK_THROUGH_ARG => options.click_through = true,
+                _ => warn!("Unknown argument: {}", arg),
+            }
+        }
+
+        options
+    }
 }
 
 /// Message for cross thread sending & receiving.
@@ -136,6 +418,44 @@ enum Message {
     Initialized,
     /// Send when new update (from url) happened.
     Updated,
+    /// Send when no source could be fetched.
+    Failed,
+}
+
+/// Where the data came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataSource {
+    /// Files of an earlier update, read on start up.
+    LocalFiles,
+    WorldState,
+    /// The warframestat.us API, used when the world state cannot be fetched.
+    WarframeStat,
+}
+
+impl Display for DataSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DataSource::LocalFiles => "Local files",
+            DataSource::WorldState => "worldState",
+            DataSource::WarframeStat => "warframestat.us",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Timing of the updates, for the status bar.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateStatus {
+    /// Source of the current data, `None` before anything has loaded.
+    pub source: Option<DataSource>,
+    /// How long the last fetch took.
+    pub duration: Option<std::time::Duration>,
+    /// True while fetching.
+    pub updating: bool,
+    /// True if the last fetch failed on every source.
+    pub failed: bool,
+    /// Refresh on the next tick, whatever the cooldown.
+    pub refresh_requested: bool,
 }
 
 /// Contains all the data the UI needs.
@@ -151,16 +471,49 @@ pub struct TennoData {
     pub invasions: Vec<Invasion>,
 
     pub storage: Storage,
+    /// Relics the user owns.
+    pub relics: RelicInventory,
+    /// Delivered notifications the UI has not shown yet.
+    pub toasts: Vec<Notification>,
+    pub update_status: UpdateStatus,
+    /// Fissures and invasions that have appeared since the user last looked at them.
+    pub unseen: Vec<NotificationKey>,
 }
 
-impl Default for TennoData {
-    fn default() -> Self {
+impl TennoData {
+    /// Data with the `storage` loaded at startup, fissures and the rest come later from the event loop.
+    pub fn new(storage: Storage) -> Self {
         Self {
             initialized: false,
             fissures: vec![],
             cetus_cycle: Default::default(),
             invasions: vec![],
-            storage: Storage::from_file(STORAGE_FILE),
+            storage,
+            relics: RelicInventory::from_file(),
+            toasts: vec![],
+            update_status: UpdateStatus::default(),
+            unseen: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+impl TennoData {
+    /// Loaded data with the default settings and no relics, nothing is read from the disk.
+    /// The Cetus cycle ends `cetus_left` from now.
+    pub fn test(fissures: Vec<Fissure>, invasions: Vec<Invasion>, cetus_left: Duration) -> Self {
+        TennoData {
+            initialized: true,
+            fissures,
+            cetus_cycle: CetusCycle {
+                expiry: Utc::now() + cetus_left,
+            },
+            invasions,
+            storage: Storage::default(),
+            relics: RelicInventory::default(),
+            toasts: vec![],
+            update_status: UpdateStatus::default(),
+            unseen: vec![],
         }
     }
 }
@@ -171,11 +524,7 @@ impl TennoData {
     pub fn has_epic_invasion(&self) -> Option<Invasion> {
         self.invasions
             .iter()
-            .find(|i| {
-                ["forma", "reactor", "catalyst"]
-                    .iter()
-                    .any(|w| i.rewards.all_rewards_string().to_lowercase().contains(w))
-            })
+            .find(|i| i.has_epic_reward())
             .map(|i| i.to_owned())
     }
 
@@ -183,11 +532,247 @@ impl TennoData {
     pub fn has_void_capture(&self) -> Option<Fissure> {
         self.fissures
             .iter()
-            .find(|f| {
-                !f.is_storm && (f.node.value == "Hepit (Void)" || f.node.value == "Ukko (Void)")
-            })
+            .find(|f| f.is_void_capture())
             .map(|f| f.to_owned())
     }
+
+    /// Returns the active fissures and void storms that the fissure filter lets through.
+    pub fn filtered_fissures(&self) -> Vec<&Fissure> {
+        self.fissures
+            .iter()
+            .filter(|f| !f.has_expired() && self.storage.shows_fissure(f))
+            .collect()
+    }
+
+    /// Returns true if the user has not looked at the fissure or invasion with the `key` yet.
+    pub fn is_new(&self, key: &NotificationKey) -> bool {
+        self.unseen.contains(key)
+    }
+
+    /// Keep track of the fissures and invasions that appear and go in the `events`.
+    /// The ones seen before, in this or an earlier session, are not new.
+    pub fn track_new(&mut self, events: &[WorldEvent]) {
+        for event in events {
+            match event {
+                WorldEvent::FissureAppeared(fissure) | WorldEvent::StormAppeared(fissure) => {
+                    let key = fissure.notification_key();
+                    if !self.storage.seen.contains(&key) {
+                        self.unseen.push(key);
+                    }
+                }
+                WorldEvent::InvasionAppeared(invasion) => {
+                    let key = invasion.notification_key();
+                    if !self.storage.seen.contains(&key) {
+                        self.unseen.push(key);
+                    }
+                }
+                WorldEvent::FissureExpired(fissure) => {
+                    let key = fissure.notification_key();
+                    self.unseen.retain(|k| *k != key);
+                }
+                WorldEvent::InvasionCompleted(invasion) => {
+                    let key = invasion.notification_key();
+                    self.unseen.retain(|k| *k != key);
+                }
+                WorldEvent::CycleChanged { .. } => {}
+            }
+        }
+    }
+
+    /// Returns how many of the shown fissures, or void storms when `storm` is true, are new.
+    pub fn unseen_fissures(&self, storm: bool) -> usize {
+        self.filtered_fissures()
+            .iter()
+            .filter(|f| f.is_storm == storm && self.is_new(&f.notification_key()))
+            .count()
+    }
+
+    /// Returns how many of the invasions are new.
+    pub fn unseen_invasions(&self) -> usize {
+        self.invasions
+            .iter()
+            .filter(|i| self.is_new(&i.notification_key()))
+            .count()
+    }
+
+    /// Mark the shown fissures, or void storms when `storm` is true, as seen.
+    pub fn mark_fissures_seen(&mut self, storm: bool) {
+        let keys = self
+            .filtered_fissures()
+            .iter()
+            .filter(|f| f.is_storm == storm)
+            .map(|f| f.notification_key())
+            .collect();
+
+        if self.mark_seen(keys) {
+            self.storage.save();
+        }
+    }
+
+    /// Mark the invasions as seen.
+    pub fn mark_invasions_seen(&mut self) {
+        let keys = self
+            .invasions
+            .iter()
+            .map(|i| i.notification_key())
+            .collect();
+
+        if self.mark_seen(keys) {
+            self.storage.save();
+        }
+    }
+
+    /// Add the `keys` to the seen ones and forget the ones that are gone.
+    /// Returns true if the seen ones have changed.
+    fn mark_seen(&mut self, keys: Vec<NotificationKey>) -> bool {
+        let current = self
+            .fissures
+            .iter()
+            .map(|f| f.notification_key())
+            .chain(self.invasions.iter().map(|i| i.notification_key()))
+            .collect::<Vec<NotificationKey>>();
+
+        let len = self.storage.seen.len();
+        self.storage.seen.retain(|k| current.contains(k));
+        let pruned = len != self.storage.seen.len();
+
+        let mut added = false;
+        for key in keys {
+            self.unseen.retain(|k| *k != key);
+            if !self.storage.seen.contains(&key) {
+                self.storage.seen.push(key);
+                added = true;
+            }
+        }
+
+        pruned || added
+    }
+
+    /// Decide how the `notification` is delivered and queue it for the UI if it should be seen.
+    /// Returns true if the notification sound should be played.
+    fn deliver(&mut self, notification: &mut Notification) -> bool {
+        notification.delivery = self.storage.delivery(notification.rule);
+
+        if notification.delivery != Delivery::Suppressed {
+            self.toasts.push(notification.clone());
+        }
+
+        notification.delivery == Delivery::Audio
+    }
+
+    /// Returns a new `Notification` for every fissure and invasion that appeared in the `events`
+    /// and matches one of the enabled notification rules, and for every reminder that is due.
+    /// Notifications already in the history are left out, so each one fires once.
+    pub fn notification_matches(&self, events: &[WorldEvent]) -> Vec<Notification> {
+        let settings = &self.storage.notification_settings;
+        let mut matches = vec![];
+
+        for event in events {
+            match event {
+                WorldEvent::FissureAppeared(fissure) | WorldEvent::StormAppeared(fissure) => {
+                    if settings.fissure_void_capture && fissure.is_void_capture() {
+                        matches.push(Notification::new(
+                            fissure.notification_key(),
+                            NotificationRule::FissureVoidCapture,
+                            Some(fissure.expiry.timestamp()),
+                        ));
+                    }
+
+                    if settings.fissure_owned_relic
+                        && settings.liked_missions.contains(&fissure.mission)
+                        && self.relics.count_for_tier(&fissure.tier) > 0
+                    {
+                        matches.push(Notification::new(
+                            fissure.notification_key(),
+                            NotificationRule::FissureOwnedRelic,
+                            Some(fissure.expiry.timestamp()),
+                        ));
+                    }
+                }
+                WorldEvent::InvasionAppeared(invasion)
+                    if settings.invasion_epic && invasion.has_epic_reward() =>
+                {
+                    matches.push(Notification::new(
+                        invasion.notification_key(),
+                        NotificationRule::InvasionEpic,
+                        None,
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        // Reminders
+        let reminders = &settings.reminders;
+        let cetus_transitions = [
+            (
+                NotificationRule::CetusNight,
+                reminders.cetus_night,
+                self.cetus_cycle.night_start(),
+            ),
+            (
+                NotificationRule::CetusDay,
+                reminders.cetus_day,
+                self.cetus_cycle.expiry,
+            ),
+        ];
+        for (rule, reminder, at) in cetus_transitions {
+            if reminder.is_due(at) {
+                matches.push(Notification::new(
+                    NotificationKey {
+                        node: "Cetus".to_string(),
+                        tier: None,
+                        activation: at.timestamp(),
+                    },
+                    rule,
+                    Some(at.timestamp()),
+                ));
+            }
+        }
+
+        for fissure in self.fissures.iter().filter(|f| {
+            reminders.fissure_expiry.is_due(f.expiry)
+                && self
+                    .storage
+                    .watched_fissures
+                    .contains(&f.notification_key())
+        }) {
+            matches.push(Notification::new(
+                fissure.notification_key(),
+                NotificationRule::FissureExpiry,
+                Some(fissure.expiry.timestamp()),
+            ));
+        }
+
+        matches.retain(|n| !self.storage.notifications.contains(n.rule, &n.key));
+        matches
+    }
+
+    /// Stop watching fissures that have expired.
+    /// Returns true if anything was removed.
+    fn prune_watched(&mut self) -> bool {
+        let len = self.storage.watched_fissures.len();
+        let fissures = &self.fissures;
+
+        self.storage.watched_fissures.retain(|k| {
+            fissures
+                .iter()
+                .any(|f| f.notification_key() == *k && !f.has_expired())
+        });
+
+        len != self.storage.watched_fissures.len()
+    }
+
+    /// Update on the next tick of the event loop, unless the last update was less than
+    /// `MIN_REFRESH_INTERVAL` ago. Returns false if the refresh has to wait.
+    pub fn refresh_now(&mut self) -> bool {
+        if self.storage.refresh_wait() > 0 {
+            return false;
+        }
+
+        self.update_status.refresh_requested = true;
+        true
+    }
 }
 
 /// The actual app.
@@ -195,26 +780,65 @@ impl TennoData {
 pub struct VoidRat {
     /// All the data the UI needs. Thread safe.
     pub data: Arc<RwLock<TennoData>>,
+    /// Snapshot replay, `None` when the data is live.
+    pub replay: Option<Arc<Replay>>,
+    /// Changes to the fissures, invasions and Cetus cycle.
+    pub events: Arc<EventBus>,
     /// A cool loop handle (seems the `l` killed a dash).
     _loop: JoinHandle<()>,
 }
 
 impl Default for VoidRat {
     fn default() -> Self {
-        Self::new()
+        Self::new(Options::default(), Storage::from_file(STORAGE_FILE))
     }
 }
 
 impl VoidRat {
-    pub fn new() -> Self {
+    pub fn new(options: Options, storage: Storage) -> Self {
         let (tx, rx) = mpsc::channel::<Message>();
-        let tenno_data = TennoData::default();
+
+        let replay = options
+            .replay
+            .as_ref()
+            .and_then(|dir| match Replay::load(dir) {
+                Ok(replay) => {
+                    replay.clock().set_speed(options.speed.unwrap_or(60.0));
+                    replay.clock().set_paused(false);
+                    clock::set(replay.clock().clone());
+
+                    Some(Arc::new(replay))
+                }
+                Err(e) => {
+                    warn!("Cannot replay: {}", e);
+                    None
+                }
+            });
+
+        let tenno_data = TennoData::new(storage);
 
         let data = Arc::new(RwLock::new(tenno_data));
         let data_clone = data.clone();
-        let _loop = thread::spawn(move || Self::event_loop(data_clone, tx, rx));
-
-        VoidRat { data, _loop }
+        let replay_clone = replay.clone();
+        let events = Arc::new(EventBus::default());
+        let events_clone = events.clone();
+        let _loop = thread::spawn(move || {
+            Self::event_loop(
+                data_clone,
+                tx,
+                rx,
+                events_clone,
+                replay_clone,
+                options.record,
+            )
+        });
+
+        VoidRat {
+            data,
+            replay,
+            events,
+            _loop,
+        }
     }
 
     /// Loop for all the things.
@@ -222,20 +846,49 @@ impl VoidRat {
     /// Loads the initial data upon app startup.
     ///
     /// Handles updating the existing data periodically.
-    fn event_loop(data: Arc<RwLock<TennoData>>, tx: Sender<Message>, rx: Receiver<Message>) {
+    ///
+    /// During a `replay` the data comes from the snapshot at the virtual time instead.
+    fn event_loop(
+        data: Arc<RwLock<TennoData>>,
+        tx: Sender<Message>,
+        rx: Receiver<Message>,
+        events: Arc<EventBus>,
+        replay: Option<Arc<Replay>>,
+        record: Option<PathBuf>,
+    ) {
         let mut initialized = false;
+        let mut diff = WorldDiff::default();
         let mut updating = false;
+        // Index of the snapshot currently shown.
+        let mut replayed = None;
+
+        // Replayed data does not belong in the history.
+        let mut archive = match replay {
+            Some(_) => None,
+            None => Archive::open()
+                .map_err(|e| warn!("Cannot open the archive: {}", e))
+                .ok(),
+        };
+
+        // Notifications, the archive and the NEW badges only look at what has changed.
+        let notification_events = events.subscribe();
+        let new_events = events.subscribe();
+        let archive_events = archive.as_ref().map(|_| events.subscribe());
 
         loop {
             if let Ok(msg) = rx.try_recv() {
                 match msg {
                     Message::Initialized => {
                         data.write().initialized = true;
+                        data.write()
+                            .update_status
+                            .source
+                            .get_or_insert(DataSource::LocalFiles);
                         initialized = true;
                     }
                     Message::Updated => {
                         // Data was updated, update the time and save to file.
-                        data.write().storage.last_update = Local::now().timestamp();
+                        data.write().storage.last_update = clock::now().timestamp();
                         data.write()
                             .storage
                             .write_to_file()
@@ -243,64 +896,38 @@ impl VoidRat {
                         // Set `updating` false since everything is done.
                         updating = false;
 
-                        //
-                        // Play notification if maybe perhaps
-                        //
-                        let mut new_noti = false;
-                        let mut storage = data.read().storage.clone();
-                        let old_notis = data.read().storage.notified.clone();
-                        // Fissure notifications
-                        if storage.noti_fissure_void_capture {
-                            if let Some(fissure) = data.read().has_void_capture() {
-                                if !old_notis
-                                    .iter()
-                                    .any(|n| n.timestamp == fissure.activation.timestamp())
-                                {
-                                    play_notification_sound();
-
-                                    storage
-                                        .notified
-                                        .push(Notification::new(fissure.activation.timestamp()));
-
-                                    new_noti = true;
-                                }
-                            }
-                        }
-                        // Invasion notifications
-                        if storage.noti_invasion_epic {
-                            if let Some(invasion) = data.read().has_epic_invasion() {
-                                if !old_notis
-                                    .iter()
-                                    .any(|n| n.timestamp == invasion.activation.timestamp())
-                                {
-                                    play_notification_sound();
-
-                                    storage
-                                        .notified
-                                        .push(Notification::new(invasion.activation.timestamp()));
-
-                                    new_noti = true;
-                                }
-                            }
-                        }
-
-                        if new_noti {
-                            data.write().storage = storage;
-                            data.write()
-                                .storage
-                                .write_to_file()
-                                .expect("Cannot write to storage file.");
-                        }
-
                         debug!("Updated!");
                     }
+                    Message::Failed => {
+                        // Try again after the cooldown.
+                        data.write().storage.last_update = clock::now().timestamp();
+                        updating = false;
+                    }
                 }
             }
 
-            if !initialized {
+            // REPLAY
+            //
+            if let Some(replay) = &replay {
+                let index = replay.current();
+
+                if replayed != Some(index) {
+                    replayed = Some(index);
+
+                    match replay.read(index) {
+                        Ok(json) => Self::load_world(&data, &WorldState {}, &json, &json, &json),
+                        Err(e) => warn!("Cannot read snapshot: {}", e),
+                    }
+
+                    if !initialized {
+                        tx.send(Message::Initialized)
+                            .expect("Cannot send initialized msg!");
+                    }
+                }
+            } else if !initialized {
                 // INITIALIZE
                 //
-                let data_path = PathBuf::from(DATA_PATH);
+                let data_path = data_path();
                 let world_state_file = &data_path.join(WORLD_STATE_DATA_PATH);
                 let fissure_file = &data_path.join("fissure.json");
                 let cetus_file = &data_path.join("cetus.json");
@@ -308,18 +935,19 @@ impl VoidRat {
 
                 // Create the data directory if it does not exist.
                 if !data_path.exists() {
-                    create_dir(&data_path).expect("Cannot create the data directory.");
+                    fs::create_dir_all(&data_path).expect("Cannot create the data directory.");
                 }
 
                 // If world state date file is missing,
                 // then get the data from url and
                 // create the file with the new data.
                 if !world_state_file.exists() {
-                    if let Some(world_data) = fetch_json_data(WORLD_STATE_URL) {
-                        fs::write(&world_state_file, world_data)
+                    let timeout = data.read().storage.request_timeout;
+                    if let Some(world_data) = fetch_json_data(WORLD_STATE_URL, timeout) {
+                        fs::write(world_state_file, world_data)
                             .expect("Unable to write world state file.");
 
-                        data.write().storage.last_update = Local::now().timestamp();
+                        data.write().storage.last_update = clock::now().timestamp();
                     }
                 }
 
@@ -334,68 +962,119 @@ impl VoidRat {
                             &fs::metadata(world_state_file).unwrap(),
                         )
                 {
-                    let p = WarframeStat {};
                     // Fissure data
                     let fissure_data = fs::read_to_string(fissure_file)
                         .expect("Something went wrong reading the file.");
-                    data.write().fissures = p.parse_fissures(&fissure_data);
                     // Cetus cycle data
                     let cetus_data = fs::read_to_string(cetus_file)
                         .expect("Something went wrong reading the file.");
-                    data.write().cetus_cycle = p.parse_cetus_cycle(&cetus_data);
                     // Invasion data
                     let invasion_data = fs::read_to_string(invasion_file)
                         .expect("Something went wrong reading the file.");
-                    data.write().invasions = p.parse_invasions(&invasion_data);
+
+                    Self::load_world(
+                        &data,
+                        &WarframeStat {},
+                        &fissure_data,
+                        &cetus_data,
+                        &invasion_data,
+                    );
 
                     tx.send(Message::Initialized)
                         .expect("Cannot send initialized msg!");
                 } else {
                     // Load data from the world state file, that definitely exists.
-                    let p = WorldState {};
-
                     let world_state_data =
-                        match fs::read_to_string(&data_path.join(WORLD_STATE_DATA_PATH)) {
+                        match fs::read_to_string(data_path.join(WORLD_STATE_DATA_PATH)) {
                             Ok(d) => d,
                             Err(e) => panic!("{}", e),
                         };
 
-                    data.write().fissures = p.parse_fissures(&world_state_data);
-                    data.write().cetus_cycle = p.parse_cetus_cycle(&world_state_data);
-                    data.write().invasions = p.parse_invasions(&world_state_data);
+                    Self::load_world(
+                        &data,
+                        &WorldState {},
+                        &world_state_data,
+                        &world_state_data,
+                        &world_state_data,
+                    );
 
                     tx.send(Message::Initialized)
                         .expect("Cannot send initialized msg!");
                 }
             }
 
+            // CHANGES
+            //
+            if initialized {
+                let changes = diff.update(&data.read());
+                for change in &changes {
+                    debug!("{}", change);
+                }
+
+                events.publish(&changes);
+
+                data.write()
+                    .track_new(&new_events.try_iter().collect::<Vec<WorldEvent>>());
+
+                // Keep the history of everything seen.
+                if let (Some(archive), Some(archive_events)) = (archive.as_mut(), &archive_events) {
+                    let changes = archive_events.try_iter().collect::<Vec<WorldEvent>>();
+                    Self::archive(archive, &changes, &data.read());
+                }
+            }
+
+            // NOTIFICATIONS
+            //
+            if initialized {
+                let changes = notification_events.try_iter().collect::<Vec<WorldEvent>>();
+                Self::notify(&data, &changes);
+            }
+
             // UPDATE
             //
             debug!("Next update in: {:?}", data.read().storage.next_update());
 
-            if data.read().storage.can_update() && !updating {
+            let refresh =
+                data.read().storage.can_update() || data.read().update_status.refresh_requested;
+            if replay.is_none() && refresh && !updating {
                 // Started updating, let us not do this every tick, heh.
                 updating = true;
+                {
+                    let status = &mut data.write().update_status;
+                    status.updating = true;
+                    status.refresh_requested = false;
+                }
 
                 debug!("Updating..");
 
                 let tx_clone = tx.clone();
                 let data_clone = data.clone();
+                let record = record.clone();
                 //
                 // New thread
                 //
                 thread::spawn(move || {
+                    let started = Instant::now();
+                    let timeout = data_clone.read().storage.request_timeout;
                     // Parse data from world state data, fresh from the oven (net).
                     let parser = WorldState {};
 
-                    if let Some(json) = fetch_json_data(WORLD_STATE_URL) {
-                        let file_path = PathBuf::from(DATA_PATH).join("world_state.json");
+                    if let Some(json) = fetch_json_data(WORLD_STATE_URL, timeout) {
+                        let file_path = data_path().join("world_state.json");
                         // Got cool json data so put it in the local file for easy re-use.
                         fs::write(&file_path, json.clone()).expect("Cannot write to file.");
 
-                        data_clone.write().fissures = parser.parse_fissures(&json);
-                        data_clone.write().cetus_cycle = parser.parse_cetus_cycle(&json);
-                        data_clone.write().invasions = parser.parse_invasions(&json);
+                        if let Some(dir) = &record {
+                            if let Err(e) = replay::record(dir, &json) {
+                                warn!("Cannot record snapshot: {}", e);
+                            }
+                        }
+
+                        Self::load_world(&data_clone, &parser, &json, &json, &json);
+                        data_clone
+                            .write()
+                            .update_status
+                            .finish(Some(DataSource::WorldState), started);
 
                         tx_clone
                             .send(Message::Updated)
@@ -405,14 +1084,16 @@ impl VoidRat {
                         warn!("Failed to fetch json data from primary source, using fallback instead.");
 
                         let fallback = WarframeStat {};
+                        let mut fetched = false;
 
                         if let Some(json) =
-                            fetch_json_data("https://api.warframestat.us/pc/fissures")
+                            fetch_json_data("https://api.warframestat.us/pc/fissures", timeout)
                         {
-                            let file_path = PathBuf::from(DATA_PATH).join("fissure.json");
+                            let file_path = data_path().join("fissure.json");
                             fs::write(&file_path, json.clone()).expect("Cannot write to file.");
 
                             data_clone.write().fissures = fallback.parse_fissures(&json);
+                            fetched = true;
 
                             tx_clone
                                 .send(Message::Updated)
@@ -420,12 +1101,13 @@ impl VoidRat {
                         }
 
                         if let Some(json) =
-                            fetch_json_data("https://api.warframestat.us/pc/cetusCycle")
+                            fetch_json_data("https://api.warframestat.us/pc/cetusCycle", timeout)
                         {
-                            let file_path = PathBuf::from(DATA_PATH).join("cetus.json");
+                            let file_path = data_path().join("cetus.json");
                             fs::write(&file_path, json.clone()).expect("Cannot write to file.");
 
                             data_clone.write().cetus_cycle = fallback.parse_cetus_cycle(&json);
+                            fetched = true;
 
                             tx_clone
                                 .send(Message::Updated)
@@ -433,17 +1115,26 @@ impl VoidRat {
                         }
 
                         if let Some(json) =
-                            fetch_json_data("https://api.warframestat.us/pc/invasions")
+                            fetch_json_data("https://api.warframestat.us/pc/invasions", timeout)
                         {
-                            let file_path = PathBuf::from(DATA_PATH).join("invasion.json");
+                            let file_path = data_path().join("invasion.json");
                             fs::write(&file_path, json.clone()).expect("Cannot write to file.");
 
                             data_clone.write().invasions = fallback.parse_invasions(&json);
+                            fetched = true;
 
                             tx_clone
                                 .send(Message::Updated)
                                 .expect("Cannot send updated msg!");
                         }
+
+                        let source = fetched.then_some(DataSource::WarframeStat);
+                        data_clone.write().update_status.finish(source, started);
+                        if !fetched {
+                            tx_clone
+                                .send(Message::Failed)
+                                .expect("Cannot send failed msg!");
+                        }
                     }
                 });
             }
@@ -452,13 +1143,109 @@ impl VoidRat {
             thread::sleep(std::time::Duration::from_millis(500));
         }
     }
+
+    /// Parse the fissures, the Cetus cycle and the invasions and swap them in under one lock,
+    /// so the changes are never looked for in a half updated world.
+    fn load_world(
+        data: &RwLock<TennoData>,
+        parser: &impl TennoParser,
+        fissure_data: &str,
+        cetus_data: &str,
+        invasion_data: &str,
+    ) {
+        let fissures = parser.parse_fissures(fissure_data);
+        let cetus_cycle = parser.parse_cetus_cycle(cetus_data);
+        let invasions = parser.parse_invasions(invasion_data);
+
+        let mut data = data.write();
+        data.fissures = fissures;
+        data.cetus_cycle = cetus_cycle;
+        data.invasions = invasions;
+    }
+
+    /// Store the fissures and invasions that appeared or went in the `events`
+    /// and drop the too old ones from the archive.
+    fn archive(archive: &mut Archive, events: &[WorldEvent], data: &TennoData) {
+        if events.is_empty() {
+            return;
+        }
+
+        let mut fissures = vec![];
+        let mut invasions = vec![];
+        for event in events {
+            match event {
+                WorldEvent::FissureAppeared(fissure)
+                | WorldEvent::StormAppeared(fissure)
+                | WorldEvent::FissureExpired(fissure) => fissures.push(fissure.clone()),
+                WorldEvent::InvasionAppeared(invasion)
+                | WorldEvent::InvasionCompleted(invasion) => invasions.push(invasion.clone()),
+                WorldEvent::CycleChanged { .. } => {}
+            }
+        }
+
+        if let Err(e) = archive
+            .record(&fissures, &invasions)
+            .and_then(|_| archive.prune(data.storage.history_retention_days))
+        {
+            warn!("Cannot update the archive: {}", e);
+        }
+    }
+
+    /// Deliver new notifications, reminders and snoozed notifications
+    /// and keep the notification history tidy.
+    fn notify(data: &Arc<RwLock<TennoData>>, events: &[WorldEvent]) {
+        let matches = data.read().notification_matches(events);
+        let due = data.write().storage.notifications.take_due_snoozes();
+        let mut play_sound = false;
+        let mut changed = !due.is_empty();
+        {
+            let mut data = data.write();
+
+            for mut notification in matches {
+                // Suppressed notifications still end up in the history.
+                if data.deliver(&mut notification) {
+                    play_sound = true;
+                }
+
+                data.storage.notifications.push(notification);
+                changed = true;
+            }
+
+            for mut notification in due {
+                notification.fired_at = clock::now().timestamp();
+
+                if data.deliver(&mut notification) {
+                    play_sound = true;
+                }
+            }
+
+            if data.storage.notifications.prune() || data.prune_watched() {
+                changed = true;
+            }
+        }
+
+        if changed {
+            data.read()
+                .storage
+                .write_to_file()
+                .expect("Cannot write to storage file.");
+        }
+
+        if play_sound {
+            play_notification_sound();
+        }
+    }
 }
 
 /// Might return json string from url.
-fn fetch_json_data(url: &str) -> Option<String> {
+fn fetch_json_data(url: &str, timeout: u64) -> Option<String> {
     debug!("Fetching {}", url);
 
-    let res = reqwest::blocking::get(url).ok()?;
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout))
+        .build()
+        .ok()?;
+    let res = client.get(url).send().ok()?;
 
     // Only write to the file if status is a success.
     if res.status().is_success() {
@@ -471,15 +1258,25 @@ fn fetch_json_data(url: &str) -> Option<String> {
 impl Fissure {
     /// Returns a `Duration` of time till fissure expires.
     pub fn till_expired(&self) -> Duration {
-        let now: DateTime<Utc> = Utc::now();
+        let now: DateTime<Utc> = clock::now();
         self.expiry - now
     }
 
     /// Returns true if the fissure has expired.
     pub fn has_expired(&self) -> bool {
-        let now: DateTime<Utc> = Utc::now();
+        let now: DateTime<Utc> = clock::now();
         self.expiry < now
     }
+
+    /// Returns how much of the lifetime has elapsed, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        progress(self.activation, self.expiry)
+    }
+
+    /// Returns true if the fissure is in the Void with Capture map.
+    pub fn is_void_capture(&self) -> bool {
+        !self.is_storm && self.node.is_void_capture()
+    }
 }
 
 impl CetusCycle {
@@ -489,20 +1286,35 @@ impl CetusCycle {
         let _day_time = 6000;
         let night_time = 3000;
 
-        let now = Utc::now().timestamp();
+        let now = clock::now().timestamp();
         let millis_left = self.expiry.timestamp() - now;
 
         millis_left >= night_time
     }
 
-    /// Returns `Duration` of time till current cycle ends.
-    pub fn cetus_till_cycle(&self) -> Duration {
-        let _day_time = 6000;
+    /// Returns the time when the night starts.
+    pub fn night_start(&self) -> DateTime<Utc> {
         let night_time = 3000;
 
-        let now = Utc::now();
+        self.expiry - Duration::seconds(night_time)
+    }
+
+    /// Returns how much of the current day or night has elapsed, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        let night_start = self.night_start();
+
+        if self.cetus_is_day() {
+            progress(self.expiry - Duration::minutes(150), night_start)
+        } else {
+            progress(night_start, self.expiry)
+        }
+    }
 
-        let night_start = self.expiry - Duration::seconds(night_time);
+    /// Returns `Duration` of time till current cycle ends.
+    pub fn cetus_till_cycle(&self) -> Duration {
+        let now = clock::now();
+
+        let night_start = self.night_start();
 
         let total_time_left = self.expiry - now;
         if (night_start - now).num_seconds() > 0 {
@@ -513,21 +1325,52 @@ impl CetusCycle {
     }
 }
 
+/// Returns how much of the time between `start` and `end` has elapsed, from 0 to 1.
+fn progress(start: DateTime<Utc>, end: DateTime<Utc>) -> f32 {
+    let total = (end - start).num_seconds();
+    if total <= 0 {
+        return 1.0;
+    }
+
+    ((clock::now() - start).num_seconds() as f32 / total as f32).clamp(0.0, 1.0)
+}
+
+impl UpdateStatus {
+    /// Fetch that `started` is done, `source` is `None` if it failed.
+    fn finish(&mut self, source: Option<DataSource>, started: Instant) {
+        self.updating = false;
+        self.duration = Some(started.elapsed());
+        self.failed = source.is_none();
+        if source.is_some() {
+            self.source = source;
+        }
+    }
+}
+
 impl Invasion {
     pub fn active_duration(&self) -> Duration {
-        let now = Utc::now();
+        let now = clock::now();
 
         now - self.activation
     }
+
+    /// Returns true if any of the rewards is a forma, orokin reactor or orokin catalyst.
+    pub fn has_epic_reward(&self) -> bool {
+        let rewards = self.rewards.all_rewards_string().to_lowercase();
+
+        ["forma", "reactor", "catalyst"]
+            .iter()
+            .any(|w| rewards.contains(w))
+    }
 }
 
-impl ToString for Reward {
-    fn to_string(&self) -> String {
+impl Display for Reward {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if self.quantity > 1 {
-            return format!("{} {}", self.quantity, self.item);
+            return write!(f, "{} {}", self.quantity, self.item);
         }
 
-        self.item.clone()
+        f.write_str(&self.item)
     }
 }
 
@@ -554,3 +1397,281 @@ pub fn play_notification_sound() {
     // Audio file has a duration of second or less.
     thread::sleep(std::time::Duration::from_secs(1));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::{QuietHours, Reminder};
+    use crate::parsers::FissureTier;
+
+    #[test]
+    fn default_storage_is_valid() {
+        assert_eq!(Storage::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_settings_are_reported() {
+        let invalid = [
+            Storage {
+                update_cooldown: MIN_REFRESH_INTERVAL - 1,
+                ..Default::default()
+            },
+            Storage {
+                data_dir: "Cargo.toml".to_string(),
+                ..Default::default()
+            },
+            Storage {
+                data_pack_url: "ftp://example.com/pack.json".to_string(),
+                ..Default::default()
+            },
+            Storage {
+                history_retention_days: 0,
+                ..Default::default()
+            },
+            Storage {
+                request_timeout: 0,
+                ..Default::default()
+            },
+        ];
+
+        for storage in invalid {
+            assert!(storage.validate().is_err(), "{:?}", storage);
+        }
+    }
+
+    #[test]
+    fn apply_settings_keeps_the_options_changed_elsewhere() {
+        let opened = Storage::default();
+        let mut settings = opened.clone();
+        settings.update_cooldown = 600;
+        settings.data_pack_url = " https://example.com/pack.json ".to_string();
+
+        // Changed from the Filter menu while the settings window was open.
+        let mut storage = opened.clone();
+        storage.fissure_filter.hidden_tiers = vec!["Lith".to_string()];
+
+        storage.apply_settings(&settings, &opened);
+
+        assert_eq!(storage.update_cooldown, 600);
+        assert_eq!(storage.data_pack_url, "https://example.com/pack.json");
+        assert_eq!(storage.fissure_filter.hidden_tiers, ["Lith"]);
+    }
+
+    /// Quiet hours that cover the whole week.
+    fn always(delivery: Delivery) -> QuietHours {
+        QuietHours {
+            start: 0,
+            end: 24 * 60,
+            delivery,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn delivery_is_audio_outside_quiet_hours() {
+        let mut storage = Storage::default();
+        storage.notification_settings.quiet_hours = vec![QuietHours {
+            days: [false; 7],
+            ..Default::default()
+        }];
+
+        assert_eq!(
+            storage.delivery(NotificationRule::CetusNight),
+            Delivery::Audio
+        );
+    }
+
+    #[test]
+    fn quietest_quiet_hours_win() {
+        let mut storage = Storage::default();
+        storage.notification_settings.quiet_hours =
+            vec![always(Delivery::Visual), always(Delivery::Audio)];
+        assert_eq!(
+            storage.delivery(NotificationRule::CetusNight),
+            Delivery::Visual
+        );
+
+        storage
+            .notification_settings
+            .quiet_hours
+            .push(always(Delivery::Suppressed));
+        assert_eq!(
+            storage.delivery(NotificationRule::CetusNight),
+            Delivery::Suppressed
+        );
+    }
+
+    #[test]
+    fn dnd_overrides_quiet_hours() {
+        let mut storage = Storage::default();
+        storage.notification_settings.quiet_hours = vec![always(Delivery::Visual)];
+        storage.dnd_until = Some(Utc::now().timestamp() + 600);
+        assert_eq!(
+            storage.delivery(NotificationRule::CetusNight),
+            Delivery::Suppressed
+        );
+
+        storage.dnd_until = Some(Utc::now().timestamp() - 600);
+        assert_eq!(
+            storage.delivery(NotificationRule::CetusNight),
+            Delivery::Visual
+        );
+    }
+
+    #[test]
+    fn muted_rules_are_suppressed() {
+        let mut storage = Storage::default();
+        storage.notifications.mute(NotificationRule::CetusNight);
+
+        assert_eq!(
+            storage.delivery(NotificationRule::CetusNight),
+            Delivery::Suppressed
+        );
+        assert_eq!(
+            storage.delivery(NotificationRule::CetusDay),
+            Delivery::Audio
+        );
+    }
+
+    /// Fires the due notifications of the `data` like the event loop does.
+    fn fire(data: &mut TennoData) -> Vec<NotificationRule> {
+        let matches = data.notification_matches(&[]);
+        let rules = matches.iter().map(|n| n.rule).collect();
+
+        for notification in matches {
+            data.storage.notifications.push(notification);
+        }
+
+        rules
+    }
+
+    fn reminder(lead_minutes: u32) -> Reminder {
+        Reminder {
+            enabled: true,
+            lead_minutes,
+        }
+    }
+
+    #[test]
+    fn reminders_are_due_within_the_lead_time() {
+        let in_ten_minutes = Utc::now() + Duration::minutes(10);
+
+        assert!(reminder(15).is_due(in_ten_minutes));
+        assert!(!reminder(5).is_due(in_ten_minutes));
+        assert!(!reminder(15).is_due(Utc::now() - Duration::minutes(1)));
+        assert!(!Reminder {
+            enabled: false,
+            ..reminder(15)
+        }
+        .is_due(in_ten_minutes));
+    }
+
+    #[test]
+    fn cetus_reminders_fire_once_per_cycle() {
+        // The night starts in three minutes.
+        let mut data = TennoData::test(vec![], vec![], Duration::minutes(53));
+        data.storage.notification_settings.reminders.cetus_night = reminder(5);
+
+        assert_eq!(fire(&mut data), [NotificationRule::CetusNight]);
+        assert!(fire(&mut data).is_empty());
+
+        // Cycles are told apart by the time the night starts.
+        data.cetus_cycle.expiry += Duration::minutes(1);
+        assert_eq!(fire(&mut data), [NotificationRule::CetusNight]);
+        assert!(fire(&mut data).is_empty());
+    }
+
+    #[test]
+    fn reminders_with_a_longer_lead_time_fire_once() {
+        // The night has ten minutes left.
+        let mut data = TennoData::test(vec![], vec![], Duration::minutes(10));
+        let reminders = &mut data.storage.notification_settings.reminders;
+        reminders.cetus_night = reminder(60);
+        reminders.cetus_day = reminder(60);
+
+        assert_eq!(fire(&mut data), [NotificationRule::CetusDay]);
+        assert!(fire(&mut data).is_empty());
+    }
+
+    #[test]
+    fn fissure_expiry_reminders_fire_once_for_watched_fissures() {
+        let hepit = Fissure {
+            expiry: Utc::now() + Duration::minutes(3),
+            ..Fissure::test("Hepit (Void)", FissureTier::Lith)
+        };
+        let ukko = Fissure {
+            expiry: hepit.expiry,
+            ..Fissure::test("Ukko (Void)", FissureTier::Axi)
+        };
+        let mut data = TennoData::test(vec![hepit.clone(), ukko], vec![], Duration::hours(2));
+        data.storage.notification_settings.reminders.fissure_expiry = reminder(30);
+        data.storage.watched_fissures = vec![hepit.notification_key()];
+
+        assert_eq!(fire(&mut data), [NotificationRule::FissureExpiry]);
+        assert_eq!(
+            data.storage.notifications.entries[0].key,
+            hepit.notification_key()
+        );
+        assert!(fire(&mut data).is_empty());
+    }
+
+    #[test]
+    fn appeared_items_are_new_until_seen() {
+        let hepit = Fissure::test("Hepit (Void)", FissureTier::Lith);
+        let storm = Fissure {
+            is_storm: true,
+            ..Fissure::test("Ani (Void)", FissureTier::Lith)
+        };
+        let kiliken = Invasion::test("Kiliken (Venus)", "Forma Blueprint");
+        let mut data = TennoData::test(
+            vec![hepit.clone(), storm.clone()],
+            vec![kiliken.clone()],
+            Duration::hours(2),
+        );
+
+        data.track_new(&[
+            WorldEvent::FissureAppeared(hepit.clone()),
+            WorldEvent::StormAppeared(storm.clone()),
+            WorldEvent::InvasionAppeared(kiliken.clone()),
+        ]);
+        assert!(data.is_new(&hepit.notification_key()));
+        assert_eq!(data.unseen_fissures(false), 1);
+        assert_eq!(data.unseen_fissures(true), 1);
+        assert_eq!(data.unseen_invasions(), 1);
+
+        // Leaving the fissures tab marks only the normal fissures as seen.
+        let keys = vec![hepit.notification_key()];
+        assert!(data.mark_seen(keys.clone()));
+        assert!(!data.is_new(&hepit.notification_key()));
+        assert_eq!(data.unseen_fissures(true), 1);
+        assert_eq!(data.storage.seen, keys);
+        assert!(!data.mark_seen(keys));
+
+        // Seen ones are not new when they appear again, e.g: after a restart.
+        data.track_new(&[WorldEvent::FissureAppeared(hepit.clone())]);
+        assert!(!data.is_new(&hepit.notification_key()));
+    }
+
+    #[test]
+    fn gone_items_are_forgotten() {
+        let hepit = Fissure::test("Hepit (Void)", FissureTier::Lith);
+        let kiliken = Invasion::test("Kiliken (Venus)", "Forma Blueprint");
+        let mut data = TennoData::test(vec![hepit.clone()], vec![], Duration::hours(2));
+
+        data.track_new(&[
+            WorldEvent::FissureAppeared(hepit.clone()),
+            WorldEvent::InvasionAppeared(kiliken.clone()),
+        ]);
+        data.track_new(&[WorldEvent::InvasionCompleted(kiliken.clone())]);
+        assert!(!data.is_new(&kiliken.notification_key()));
+
+        assert!(data.mark_seen(vec![hepit.notification_key()]));
+        data.fissures.clear();
+        data.track_new(&[WorldEvent::FissureExpired(hepit)]);
+
+        // The seen list only keeps what is still around.
+        assert!(data.mark_seen(vec![]));
+        assert!(data.storage.seen.is_empty());
+        assert!(data.unseen.is_empty());
+    }
+}
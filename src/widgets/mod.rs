@@ -1,9 +1,11 @@
+use crate::theme;
 use crate::widgets::toggle_button::ToggledButton;
 use eframe::egui::style::Margin;
 use eframe::egui::{
-    Color32, Frame, InnerResponse, Label, Response, RichText, Rounding, Stroke, Ui, Vec2, Widget,
-    WidgetText,
+    Color32, Frame, InnerResponse, Label, Pos2, Response, RichText, Rounding, Sense, Shape, Stroke,
+    TextStyle, Ui, Vec2, Widget, WidgetText,
 };
+use std::f32::consts::TAU;
 
 pub mod colored_label;
 mod toggle_button;
@@ -39,11 +41,14 @@ pub trait UiExt {
         selected_value: Value,
         text: impl Into<WidgetText>,
     ) -> Response;
+
+    /// Small "NEW" badge for things the user has not looked at yet.
+    fn new_badge(&mut self) -> Response;
+
+    /// Ring filled clockwise from the top by the `fraction` from 0 to 1.
+    fn progress_ring(&mut self, fraction: f32, color: impl Into<Color32>) -> Response;
 }
 
-///
-///
-///
 impl UiExt for eframe::egui::Ui {
     fn badge_label(
         &mut self,
@@ -103,4 +108,43 @@ impl UiExt for eframe::egui::Ui {
         }
         response
     }
+
+    fn new_badge(&mut self) -> Response {
+        let theme = theme::current();
+        let (fill, border) = theme.plenty.colors();
+
+        self.badge_frame(fill, border, |ui| {
+            ui.colored_label(
+                theme::color(theme.badge_text),
+                RichText::new("NEW").small().strong(),
+            )
+        })
+        .response
+    }
+
+    fn progress_ring(&mut self, fraction: f32, color: impl Into<Color32>) -> Response {
+        let color = color.into();
+        let size = self.text_style_height(&TextStyle::Monospace);
+        let (rect, response) = self.allocate_exact_size(Vec2::splat(size), Sense::hover());
+
+        let center = rect.center();
+        let radius = size / 2.0 - 1.0;
+        let painter = self.painter();
+        painter.circle_stroke(center, radius, Stroke::new(1.0, color.linear_multiply(0.3)));
+
+        let fraction = fraction.clamp(0.0, 1.0);
+        // One point every few degrees is smooth enough at this size.
+        let steps = (fraction * 64.0).ceil() as usize;
+        if steps > 0 {
+            let points = (0..=steps)
+                .map(|i| {
+                    let angle = TAU * fraction * i as f32 / steps as f32 - TAU / 4.0;
+                    center + radius * Vec2::angled(angle)
+                })
+                .collect::<Vec<Pos2>>();
+            painter.add(Shape::line(points, Stroke::new(2.0, color)));
+        }
+
+        response.on_hover_text(format!("{:.0}%", fraction * 100.0))
+    }
 }
@@ -1,6 +1,5 @@
 use eframe::egui::{
-    Color32, NumExt, Response, Sense, Stroke, TextStyle, Ui, Widget, WidgetInfo, WidgetText,
-    WidgetType,
+    NumExt, Response, Sense, TextStyle, Ui, Widget, WidgetInfo, WidgetText, WidgetType,
 };
 
 /// Button/Label that has different style when `selected` is true or false.
@@ -52,15 +51,9 @@ impl Widget for ToggledButton {
                 ui.painter()
                     .rect(rect, visuals.rounding, visuals.bg_fill, visuals.bg_stroke);
             } else {
-                ui.painter().rect(
-                    rect,
-                    visuals.rounding,
-                    Color32::LIGHT_GRAY,
-                    Stroke {
-                        width: 1.0,
-                        color: Color32::GRAY,
-                    },
-                );
+                let inactive = ui.style().visuals.widgets.inactive;
+                ui.painter()
+                    .rect(rect, visuals.rounding, inactive.bg_fill, inactive.bg_stroke);
             }
 
             text.paint_with_visuals(ui.painter(), text_pos, &visuals);